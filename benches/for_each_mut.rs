@@ -0,0 +1,42 @@
+use arena::{
+	collection::Arena,
+	referent::{Id, Referent},
+};
+use divan::Bencher;
+
+fn main() {
+	divan::main();
+}
+
+fn sparse_arena(len: usize) -> Arena<Id, u32> {
+	let mut arena = Arena::with_capacity(len);
+	let keys: Vec<_> = (0..len as u32).map(|value| arena.insert(value)).collect();
+
+	for &key in keys.iter().step_by(16) {
+		arena.remove(key);
+	}
+
+	arena
+}
+
+/// Adds the index of every key to its value with a direct scan of the slots.
+#[divan::bench(args = [1_000, 100_000])]
+fn for_each_mut(bencher: Bencher, len: usize) {
+	let mut arena = sparse_arena(len);
+
+	bencher.bench_local(|| {
+		arena.for_each_mut(|key, value| *value = value.wrapping_add(key.index()));
+	});
+}
+
+/// Adds the index of every key to its value through the mutable iterator.
+#[divan::bench(args = [1_000, 100_000])]
+fn iter_mut_for_each(bencher: Bencher, len: usize) {
+	let mut arena = sparse_arena(len);
+
+	bencher.bench_local(|| {
+		arena
+			.iter_mut()
+			.for_each(|(key, value)| *value = value.wrapping_add(key.index()));
+	});
+}
@@ -0,0 +1,23 @@
+use arena::{collection::Arena, referent::Id};
+use divan::Bencher;
+
+fn main() {
+	divan::main();
+}
+
+/// Looks up every key of an arena with one in every 16 slots freed.
+#[divan::bench(args = [1_000, 100_000])]
+fn get_mostly_occupied(bencher: Bencher, len: usize) {
+	let mut arena = Arena::<Id, u32>::with_capacity(len);
+	let keys: Vec<_> = (0..len as u32).map(|value| arena.insert(value)).collect();
+
+	for &key in keys.iter().step_by(16) {
+		arena.remove(key);
+	}
+
+	bencher.bench_local(|| {
+		keys.iter()
+			.filter_map(|&key| arena.get(divan::black_box(key)))
+			.fold(0_u32, |sum, &value| sum.wrapping_add(value))
+	});
+}
@@ -0,0 +1,61 @@
+use arena::{collection::Arena, referent::Id};
+use divan::{AllocProfiler, Bencher};
+
+#[global_allocator]
+static ALLOC: AllocProfiler = AllocProfiler::system();
+
+fn main() {
+	divan::main();
+}
+
+#[divan::bench(args = [1_000, 100_000, 10_000_000])]
+fn with_capacity(bencher: Bencher, capacity: usize) {
+	bencher.bench(|| Arena::<Id, u32>::with_capacity(divan::black_box(capacity)));
+}
+
+#[divan::bench(args = [1_000, 100_000, 10_000_000])]
+fn reserve_twice(bencher: Bencher, capacity: usize) {
+	bencher.bench(|| {
+		let mut arena = Arena::<Id, u32>::with_capacity(divan::black_box(capacity / 2));
+
+		arena.reserve(capacity);
+		arena
+	});
+}
+
+/// Inserts one value at a time after a small reservation, as code that does
+/// not know its final size would.
+#[divan::bench(args = [100, 1_000])]
+fn reserve_insert_cycles(bencher: Bencher, len: u32) {
+	bencher.bench(|| {
+		let mut arena = Arena::<Id, u32>::new();
+
+		for value in 0..divan::black_box(len) {
+			arena.reserve(1);
+
+			let _key = arena.insert(value);
+		}
+
+		arena
+	});
+}
+
+/// The same cycles over a boxed slice, converted to a `Vec` and back on every
+/// reservation, as the storage used to be.
+#[divan::bench(args = [100, 1_000])]
+fn boxed_slice_cycles(bencher: Bencher, len: u32) {
+	bencher.bench(|| {
+		let mut slots: Box<[Option<u32>]> = Box::new([]);
+
+		for value in 0..divan::black_box(len) {
+			let mut vec = slots.into_vec();
+
+			vec.reserve(1);
+			vec.push(Some(value));
+
+			slots = vec.into_boxed_slice();
+		}
+
+		slots
+	});
+}
@@ -0,0 +1,272 @@
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::{
+	element::Element,
+	referent::{Referent, Similar},
+};
+
+type Chunk<Key, Value, const CHUNK: usize> =
+	Box<[Element<<Key as Referent>::Version, <Key as Referent>::Index, Value>; CHUNK]>;
+
+/// A [`ChunkedArena`] is like an [`Arena`](crate::collection::Arena), but its slots
+/// are stored in separately allocated chunks of `CHUNK` slots each. It grows by
+/// adding chunks, so no single allocation is ever larger than one chunk and existing
+/// slots are never moved.
+///
+/// Keys address the slot `index % CHUNK` of the chunk `index / CHUNK`.
+pub struct ChunkedArena<Key: Referent, Value, const CHUNK: usize> {
+	chunks: Vec<Chunk<Key, Value, CHUNK>>,
+	len: usize,
+	next: usize,
+}
+
+impl<Key: Referent, Value, const CHUNK: usize> Default for ChunkedArena<Key, Value, CHUNK> {
+	#[inline]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<Key: Referent, Value, const CHUNK: usize> ChunkedArena<Key, Value, CHUNK> {
+	/// Creates a new, empty [`ChunkedArena`]. Fails to compile if `CHUNK` is zero.
+	#[inline]
+	#[must_use]
+	pub const fn new() -> Self {
+		const { assert!(CHUNK != 0, "`CHUNK` should not be zero") };
+
+		Self {
+			chunks: Vec::new(),
+			len: 0,
+			next: 0,
+		}
+	}
+
+	/// Returns the number of elements in the [`ChunkedArena`].
+	#[inline]
+	#[must_use]
+	pub const fn len(&self) -> usize {
+		self.len
+	}
+
+	/// Returns `true` if the [`ChunkedArena`] contains no elements.
+	#[inline]
+	#[must_use]
+	pub const fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+
+	/// Returns the number of elements the [`ChunkedArena`] can hold without
+	/// allocating another chunk.
+	#[inline]
+	#[must_use]
+	pub const fn capacity(&self) -> usize {
+		self.chunks.len() * CHUNK
+	}
+
+	/// Returns the number of chunks allocated by the [`ChunkedArena`].
+	#[inline]
+	#[must_use]
+	pub const fn chunk_count(&self) -> usize {
+		self.chunks.len()
+	}
+
+	fn element(&self, index: usize) -> Option<&Element<Key::Version, Key::Index, Value>> {
+		self.chunks
+			.get(index / CHUNK)
+			.map(|chunk| &chunk[index % CHUNK])
+	}
+
+	fn element_mut(
+		&mut self,
+		index: usize,
+	) -> Option<&mut Element<Key::Version, Key::Index, Value>> {
+		self.chunks
+			.get_mut(index / CHUNK)
+			.map(|chunk| &mut chunk[index % CHUNK])
+	}
+
+	/// Adds a chunk of vacant slots, unless a `Key::Index` can not represent
+	/// every slot of it along with the end of the free list.
+	fn grow(&mut self) -> Option<()> {
+		let start = self.capacity();
+
+		Key::Index::try_from_checked(start.checked_add(CHUNK)?)?;
+
+		let chunk: Box<[_]> = (start..start + CHUNK)
+			.map(|index| Element::Vacant {
+				version: Key::Version::MIN,
+				next: Key::Index::try_from_checked(index + 1).unwrap_or_else(|| unreachable!()),
+			})
+			.collect();
+
+		self.chunks
+			.push(chunk.try_into().unwrap_or_else(|_| unreachable!()));
+
+		Some(())
+	}
+
+	/// Returns a reference to the value corresponding to the given key.
+	#[inline]
+	#[must_use]
+	pub fn get(&self, key: Key) -> Option<&Value> {
+		self.element(key.index().try_into_unchecked())
+			.and_then(|element| element.get(key.version()))
+	}
+
+	/// Returns a mutable reference to the value corresponding to the given key.
+	#[inline]
+	#[must_use]
+	pub fn get_mut(&mut self, key: Key) -> Option<&mut Value> {
+		self.element_mut(key.index().try_into_unchecked())
+			.and_then(|element| element.get_mut(key.version()))
+	}
+
+	/// Attempts to insert a value into the [`ChunkedArena`], returning the key if
+	/// successful. A new chunk is allocated if all slots are occupied.
+	#[must_use]
+	pub fn try_insert(&mut self, value: Value) -> Option<Key> {
+		if self.next == self.capacity() {
+			self.grow()?;
+		}
+
+		let index = Key::Index::try_from_checked(self.next).unwrap_or_else(|| unreachable!());
+		let (version, next) = self
+			.element_mut(self.next)
+			.unwrap_or_else(|| unreachable!())
+			.set(value);
+
+		self.len += 1;
+		self.next = next.try_into_unchecked();
+
+		Some(Key::new(index, version))
+	}
+
+	/// Inserts a value into the [`ChunkedArena`], returning the key.
+	///
+	/// # Panics
+	///
+	/// Panics if the [`ChunkedArena`] is at capacity.
+	#[inline]
+	#[must_use]
+	pub fn insert(&mut self, value: Value) -> Key {
+		self.try_insert(value).expect("should be able to insert")
+	}
+
+	/// Attempts to remove a key from the [`ChunkedArena`], returning the value if successful.
+	#[must_use]
+	pub fn try_remove(&mut self, key: Key) -> Option<Value> {
+		let next = Key::Index::try_from_checked(self.next).unwrap_or_else(|| unreachable!());
+		let value = self
+			.element_mut(key.index().try_into_unchecked())
+			.filter(|element| element.get(key.version()).is_some())
+			.and_then(|element| element.reset(next))?;
+
+		self.len -= 1;
+		self.next = key.index().try_into_unchecked();
+
+		Some(value)
+	}
+
+	/// Removes a key from the [`ChunkedArena`], returning the value.
+	///
+	/// # Panics
+	///
+	/// Panics if the key is not present in the [`ChunkedArena`].
+	#[inline]
+	pub fn remove(&mut self, key: Key) -> Value {
+		self.try_remove(key).expect("should be able to remove")
+	}
+
+	/// Returns an iterator over the keys and values of the [`ChunkedArena`],
+	/// in ascending index order across all chunks.
+	#[inline]
+	pub fn iter(&self) -> impl Iterator<Item = (Key, &Value)> {
+		self.chunks
+			.iter()
+			.flat_map(|chunk| chunk.iter())
+			.enumerate()
+			.filter_map(|(index, element)| {
+				let index = Key::Index::try_from_checked(index)?;
+				let value = element.as_ref()?;
+
+				Some((Key::new(index, element.version()), value))
+			})
+	}
+
+	/// Returns a mutable iterator over the keys and values of the [`ChunkedArena`],
+	/// in ascending index order across all chunks.
+	#[inline]
+	pub fn iter_mut(&mut self) -> impl Iterator<Item = (Key, &mut Value)> {
+		self.chunks
+			.iter_mut()
+			.flat_map(|chunk| chunk.iter_mut())
+			.enumerate()
+			.filter_map(|(index, element)| {
+				let index = Key::Index::try_from_checked(index)?;
+				let version = element.version();
+				let value = element.as_mut()?;
+
+				Some((Key::new(index, version), value))
+			})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		chunked::ChunkedArena,
+		referent::{Id, Referent},
+	};
+
+	#[test]
+	fn grow_by_chunks() {
+		let mut arena = ChunkedArena::<Id, usize, 4>::new();
+
+		let keys: Vec<_> = (0..10).map(|i| arena.insert(i)).collect();
+
+		assert_eq!(arena.chunk_count(), 3);
+		assert_eq!(arena.capacity(), 12);
+		assert!(keys
+			.iter()
+			.enumerate()
+			.all(|(i, &key)| arena.get(key) == Some(&i)));
+
+		arena.remove(keys[3]);
+		arena.remove(keys[4]);
+
+		let a = arena.insert(40);
+
+		assert_eq!(a.index(), 4);
+		assert_eq!(arena.get(keys[4]), None);
+		assert_eq!(arena.get(a), Some(&40));
+
+		*arena.get_mut(keys[5]).unwrap() += 100;
+
+		assert!(arena
+			.iter()
+			.map(|(_, &value)| value)
+			.eq([0, 1, 2, 40, 105, 6, 7, 8, 9]));
+
+		assert_eq!(arena.len(), 9);
+	}
+
+	#[test]
+	fn chunk_limit() {
+		let mut arena = ChunkedArena::<Id<u8>, u8, 100>::new();
+
+		for i in 0..200 {
+			let _key = arena.insert(i as u8);
+		}
+
+		assert_eq!(arena.try_insert(0), None);
+		assert_eq!(arena.chunk_count(), 2);
+
+		for (_, value) in arena.iter_mut() {
+			*value = value.wrapping_add(1);
+		}
+
+		assert!(arena
+			.iter()
+			.all(|(key, &value)| value == key.index().wrapping_add(1)));
+	}
+}
@@ -0,0 +1,193 @@
+use alloc::vec::Vec;
+
+use crate::{collection::Arena, referent::Referent};
+
+/// An identifier for a group of keys that can be revoked together.
+///
+/// Created by the [`CohortArena::new_cohort`] method.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
+pub struct CohortId(usize);
+
+#[derive(Clone, Debug)]
+struct Member<Value> {
+	cohort: Option<(CohortId, u64)>,
+	value: Value,
+}
+
+impl<Value> Member<Value> {
+	fn is_live(&self, epochs: &[u64]) -> bool {
+		self.cohort
+			.is_none_or(|(cohort, epoch)| epochs[cohort.0] == epoch)
+	}
+}
+
+/// A [`CohortArena`] is an [`Arena`] whose keys can be minted in cohorts.
+/// Revoking a cohort makes all of its keys resolve to nothing, without
+/// freeing their slots. This acts as a generational overlay on top of the
+/// per-slot versions of the [`Arena`].
+#[derive(Clone)]
+pub struct CohortArena<Key: Referent, Value> {
+	arena: Arena<Key, Member<Value>>,
+	epochs: Vec<u64>,
+}
+
+impl<Key: Referent, Value> Default for CohortArena<Key, Value> {
+	#[inline]
+	fn default() -> Self {
+		Self {
+			arena: Arena::new(),
+			epochs: Vec::new(),
+		}
+	}
+}
+
+impl<Key: Referent, Value> CohortArena<Key, Value> {
+	/// Creates a new, empty [`CohortArena`].
+	#[inline]
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Returns the number of elements in the [`CohortArena`], including
+	/// those belonging to revoked cohorts.
+	#[inline]
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.arena.len()
+	}
+
+	/// Returns `true` if the [`CohortArena`] contains no elements.
+	#[inline]
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.arena.is_empty()
+	}
+
+	/// Creates a new cohort that keys can be minted in.
+	#[inline]
+	#[must_use]
+	pub fn new_cohort(&mut self) -> CohortId {
+		self.epochs.push(0);
+
+		CohortId(self.epochs.len() - 1)
+	}
+
+	/// Revokes all keys minted in the cohort so far. The cohort may still
+	/// be used to mint new keys afterwards.
+	///
+	/// # Panics
+	///
+	/// Panics if the cohort was not created by this [`CohortArena`].
+	#[inline]
+	pub fn revoke_cohort(&mut self, cohort: CohortId) {
+		self.epochs[cohort.0] += 1;
+	}
+
+	/// Inserts a value that does not belong to any cohort, returning the key.
+	///
+	/// # Panics
+	///
+	/// Panics if the [`CohortArena`] is at capacity.
+	#[inline]
+	#[must_use]
+	pub fn insert(&mut self, value: Value) -> Key {
+		self.arena.insert(Member {
+			cohort: None,
+			value,
+		})
+	}
+
+	/// Inserts a value in the given cohort, returning the key.
+	///
+	/// # Panics
+	///
+	/// Panics if the [`CohortArena`] is at capacity or if the cohort was
+	/// not created by this [`CohortArena`].
+	#[inline]
+	#[must_use]
+	pub fn insert_in_cohort(&mut self, cohort: CohortId, value: Value) -> Key {
+		let epoch = self.epochs[cohort.0];
+
+		self.arena.insert(Member {
+			cohort: Some((cohort, epoch)),
+			value,
+		})
+	}
+
+	/// Returns a reference to the value corresponding to the given key,
+	/// unless its cohort has been revoked.
+	#[inline]
+	#[must_use]
+	pub fn get(&self, key: Key) -> Option<&Value> {
+		self.arena
+			.get(key)
+			.filter(|member| member.is_live(&self.epochs))
+			.map(|member| &member.value)
+	}
+
+	/// Returns a mutable reference to the value corresponding to the given key,
+	/// unless its cohort has been revoked.
+	#[inline]
+	#[must_use]
+	pub fn get_mut(&mut self, key: Key) -> Option<&mut Value> {
+		let epochs = &self.epochs;
+
+		self.arena
+			.get_mut(key)
+			.filter(|member| member.is_live(epochs))
+			.map(|member| &mut member.value)
+	}
+
+	/// Attempts to remove a key from the [`CohortArena`], returning the value if
+	/// successful. Keys of revoked cohorts can not be removed this way.
+	#[inline]
+	#[must_use]
+	pub fn try_remove(&mut self, key: Key) -> Option<Value> {
+		self.get(key)?;
+
+		self.arena.try_remove(key).map(|member| member.value)
+	}
+
+	/// Frees the slots of all elements belonging to revoked cohorts,
+	/// returning how many were freed.
+	#[inline]
+	pub fn purge_revoked(&mut self) -> usize {
+		let len = self.arena.len();
+		let epochs = &self.epochs;
+
+		self.arena.retain(|_, member| member.is_live(epochs));
+
+		len - self.arena.len()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{cohort::CohortArena, referent::Id};
+
+	#[test]
+	fn revoke_cohort() {
+		let mut arena = CohortArena::<Id, u32>::new();
+
+		let first = arena.new_cohort();
+		let second = arena.new_cohort();
+
+		let a = arena.insert_in_cohort(first, 10);
+		let b = arena.insert_in_cohort(second, 20);
+		let c = arena.insert(30);
+
+		arena.revoke_cohort(first);
+
+		assert_eq!(arena.get(a), None);
+		assert_eq!(arena.get(b), Some(&20));
+		assert_eq!(arena.get(c), Some(&30));
+		assert_eq!(arena.len(), 3);
+
+		let d = arena.insert_in_cohort(first, 40);
+
+		assert_eq!(arena.get(d), Some(&40));
+		assert_eq!(arena.purge_revoked(), 1);
+		assert_eq!(arena.len(), 3);
+	}
+}
@@ -1,6 +1,17 @@
 use core::{
-	fmt::Debug,
-	ops::{Index, IndexMut},
+	cell::Cell,
+	cmp::{Ordering, Reverse},
+	fmt::{Debug, Display, Formatter},
+	hash::{Hash, Hasher},
+	ops::{Deref, Index, IndexMut},
+	pin::Pin,
+};
+
+use alloc::{
+	boxed::Box,
+	collections::{BTreeMap, BinaryHeap, VecDeque},
+	vec,
+	vec::Vec,
 };
 
 use crate::{
@@ -8,37 +19,168 @@ use crate::{
 	referent::{try_transform, Referent, Similar},
 };
 
+// Like `Vec`, skip the tiny capacities when growing from nothing.
+const MIN_NON_ZERO_CAPACITY: usize = 4;
+
+/// A callback for when the storage of an [`Arena`] grows. It is not carried
+/// over to clones, as closures can not be cloned in general.
+pub(crate) struct ReallocHook(Option<Box<dyn FnMut(usize, usize) + Send + Sync>>);
+
+impl ReallocHook {
+	fn call(&mut self, old_capacity: usize, new_capacity: usize) {
+		if let Some(hook) = &mut self.0 {
+			hook(old_capacity, new_capacity);
+		}
+	}
+}
+
+impl Clone for ReallocHook {
+	#[inline]
+	fn clone(&self) -> Self {
+		Self(None)
+	}
+}
+
 /// An [`Arena`] is a collection of values that can be accessed by a [`Referent`].
 /// It is similar to a `Vec`, but it has stable and reusable indices.
-#[derive(Clone)]
 pub struct Arena<Key: Referent, Value> {
 	pub(crate) elements: List<Key::Version, Key::Index, Value>,
 	pub(crate) len: Key::Index,
+	pub(crate) tombstones: usize,
 	pub(crate) next: Key::Index,
+	pub(crate) max_occupied: Option<Key::Index>,
+	pub(crate) version_floor: Key::Version,
+	pub(crate) quarantine: VecDeque<Key::Index>,
+	pub(crate) quarantine_limit: usize,
+	#[cfg(debug_assertions)]
+	pub(crate) read_only: bool,
+	pub(crate) epoch: u64,
+	pub(crate) realloc_hook: ReallocHook,
+
+	#[cfg(feature = "stats")]
+	pub(crate) stats: crate::stats::AtomicLookupStats,
 }
 
-impl<Key: Referent, Value> Default for Arena<Key, Value> {
+impl<Key: Referent, Value: Clone> Clone for Arena<Key, Value> {
 	#[inline]
-	fn default() -> Self {
+	fn clone(&self) -> Self {
 		Self {
-			elements: List::default(),
-			len: Key::Index::MIN,
-			next: Key::Index::MIN,
+			elements: self.elements.clone(),
+			len: self.len,
+			tombstones: self.tombstones,
+			next: self.next,
+			max_occupied: self.max_occupied,
+			version_floor: self.version_floor,
+			quarantine: self.quarantine.clone(),
+			quarantine_limit: self.quarantine_limit,
+			#[cfg(debug_assertions)]
+			read_only: self.read_only,
+			epoch: self.epoch,
+			realloc_hook: self.realloc_hook.clone(),
+
+			#[cfg(feature = "stats")]
+			stats: self.stats.clone(),
+		}
+	}
+
+	/// Copies the slots of `source` into the existing storage, only allocating if
+	/// `source` has more slots. Any slots past those of `source` are kept as
+	/// vacant slots at the end of the free list, so the capacity is never
+	/// reduced. The realloc hook of the [`Arena`] is kept.
+	fn clone_from(&mut self, source: &Self) {
+		let old_capacity = self.capacity();
+
+		self.elements.clone_from(&source.elements);
+		self.fill_vacant();
+
+		self.len = source.len;
+		self.tombstones = source.tombstones;
+		self.next = source.next;
+		self.max_occupied = source.max_occupied;
+		self.version_floor = source.version_floor;
+		self.quarantine.clone_from(&source.quarantine);
+		self.quarantine_limit = source.quarantine_limit;
+		#[cfg(debug_assertions)]
+		{
+			self.read_only = source.read_only;
+		}
+		self.epoch = source.epoch;
+
+		#[cfg(feature = "stats")]
+		self.stats.clone_from(&source.stats);
+
+		if self.capacity() > old_capacity {
+			self.realloc_hook.call(old_capacity, self.capacity());
 		}
 	}
 }
 
+impl<Key: Referent, Value> Default for Arena<Key, Value> {
+	#[inline]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
 impl<Key: Referent, Value> Arena<Key, Value> {
 	/// Creates a new, empty [`Arena`].
+	///
+	/// Fails to compile if `Key::Index` is wider than `usize`, as such indices
+	/// could not address the backing storage.
+	///
+	/// ```compile_fail
+	/// # use arena::{collection::Arena, referent::{Id, Similar}};
+	/// #[derive(Clone, Copy)]
+	/// struct Wide(u128);
+	///
+	/// impl Similar<usize> for Wide {
+	///     const MIN: Self = Self(0);
+	///     const MAX: Self = Self(u128::MAX);
+	///
+	///     fn try_from_checked(value: usize) -> Option<Self> {
+	///         Some(Self(value as u128))
+	///     }
+	///
+	///     fn try_into_unchecked(self) -> usize {
+	///         self.0 as usize
+	///     }
+	/// }
+	///
+	/// let _arena = Arena::<Id<Wide>, u32>::new();
+	/// ```
 	#[inline]
 	#[must_use]
-	pub fn new() -> Self {
-		// This will be `const` whenever a safe `const` way of initializing
-		// an empty array `Box` is available.
-		Self::default()
+	pub const fn new() -> Self {
+		const {
+			assert!(
+				size_of::<Key::Index>() <= size_of::<usize>(),
+				"`Key::Index` should not be wider than `usize`"
+			);
+		};
+
+		Self {
+			elements: List::new(),
+			len: Key::Index::MIN,
+			tombstones: 0,
+			next: Key::Index::MIN,
+			max_occupied: None,
+			version_floor: Key::Version::MIN,
+			quarantine: VecDeque::new(),
+			quarantine_limit: 0,
+			#[cfg(debug_assertions)]
+			read_only: false,
+			epoch: 0,
+			realloc_hook: ReallocHook(None),
+
+			#[cfg(feature = "stats")]
+			stats: crate::stats::AtomicLookupStats::new(),
+		}
 	}
 
 	/// Creates a new, empty [`Arena`] with the specified capacity.
+	///
+	/// The first `capacity` insertions are guaranteed not to reallocate, as long
+	/// as a `Key::Index` can represent that many elements.
 	#[inline]
 	#[must_use]
 	pub fn with_capacity(capacity: usize) -> Self {
@@ -49,298 +191,4218 @@ impl<Key: Referent, Value> Arena<Key, Value> {
 		arena
 	}
 
-	/// Returns the number of elements the [`Arena`] can hold without reallocating.
-	#[inline]
+	/// Creates a new, empty [`Arena`] whose first insertions will use the slots
+	/// in the given order. Duplicate indices are ignored, and any slots not in
+	/// `order` are used afterwards in ascending order.
+	///
+	/// # Panics
+	///
+	/// Panics if any index is `Key::Index::MAX`.
 	#[must_use]
-	pub const fn capacity(&self) -> usize {
-		self.elements.len()
-	}
+	pub fn with_free_order(order: Vec<Key::Index>) -> Self {
+		let capacity = order
+			.iter()
+			.map(|&index| index.try_into_unchecked() + 1)
+			.max()
+			.unwrap_or(0);
 
-	/// Returns the number of elements in the [`Arena`].
-	#[inline]
-	#[must_use]
-	pub fn len(&self) -> usize {
-		self.len.try_into_unchecked()
-	}
+		let mut next =
+			Key::Index::try_from_checked(capacity).expect("index should be less than `MAX`");
 
-	/// Returns `true` if the [`Arena`] contains no elements.
-	#[inline]
-	#[must_use]
-	pub fn is_empty(&self) -> bool {
-		self.len.try_into_unchecked() == Key::Index::MIN.try_into_unchecked()
-	}
+		let mut seen = vec![false; capacity];
+		let mut chain: Vec<usize> = order
+			.into_iter()
+			.map(Similar::try_into_unchecked)
+			.filter(|&index| !core::mem::replace(&mut seen[index], true))
+			.collect();
 
-	/// Returns a reference to the value corresponding to the given key.
-	#[inline]
-	#[must_use]
-	pub fn get(&self, key: Key) -> Option<&Value> {
-		self.elements
-			.get(key.index().try_into_unchecked())
-			.and_then(|element| element.get(key.version()))
-	}
+		chain.extend((0..capacity).filter(|&index| !seen[index]));
 
-	/// Returns a mutable reference to the value corresponding to the given key.
-	#[inline]
-	#[must_use]
-	pub fn get_mut(&mut self, key: Key) -> Option<&mut Value> {
-		self.elements
-			.get_mut(key.index().try_into_unchecked())
-			.and_then(|element| element.get_mut(key.version()))
+		let mut elements: Vec<_> = (0..capacity)
+			.map(|_| Element::Vacant {
+				version: Key::Version::MIN,
+				next,
+			})
+			.collect();
+
+		for &index in chain.iter().rev() {
+			elements[index] = Element::Vacant {
+				version: Key::Version::MIN,
+				next,
+			};
+
+			next = Key::Index::try_from_checked(index).unwrap_or_else(|| unreachable!());
+		}
+
+		Self {
+			elements,
+			next,
+			..Self::default()
+		}
 	}
 
-	/// Returns the number of indices needed to store the all the elements in the [`Arena`]
-	#[inline]
+	/// Creates a new [`Arena`] where the slot at each position is occupied if the
+	/// corresponding item is `Some`. All slots start at the minimum version.
+	///
+	/// # Panics
+	///
+	/// Panics if a `Key::Index` can not represent the number of slots.
 	#[must_use]
-	pub fn indices_needed(&self) -> usize {
-		self.keys()
-			.next_back()
-			.map_or(0, |id| id.index().try_into_unchecked() + 1)
+	pub fn from_slots(slots: impl IntoIterator<Item = Option<Value>>) -> Self {
+		let elements: Vec<_> = slots
+			.into_iter()
+			.map(|slot| match slot {
+				Some(value) => Element::Occupied {
+					version: Key::Version::MIN,
+					value,
+				},
+				None => Element::Vacant {
+					version: Key::Version::MIN,
+					next: Key::Index::MIN,
+				},
+			})
+			.collect();
+
+		let len = elements
+			.iter()
+			.filter(|element| element.as_ref().is_some())
+			.count();
+
+		assert!(
+			Key::Index::try_from_checked(elements.len()).is_some(),
+			"slot count should be representable by `Key::Index`"
+		);
+
+		let mut arena = Self {
+			elements,
+			len: Key::Index::try_from_checked(len).unwrap_or_else(|| unreachable!()),
+			..Self::default()
+		};
+
+		arena.fill_vacant();
+		arena.rethread();
+		arena.max_occupied = arena
+			.elements
+			.len()
+			.checked_sub(1)
+			.and_then(Key::Index::try_from_checked);
+		arena.shrink_max_occupied();
+
+		arena
 	}
 
-	/// Reserves capacity for `additional` more elements to be inserted. Less elements
-	/// may be inserted if a `Key::Index` cannot represent the new capacity.
-	pub fn reserve_exact(&mut self, additional: usize) {
-		let capacity = Key::Index::MAX
-			.try_into_unchecked()
-			.min(additional + self.len());
+	/// Rebuilds the free list so that vacant slots are reused in ascending order.
+	/// Quarantined slots are left out, and are released as usual.
+	fn rethread(&mut self) {
+		let mut quarantined = vec![false; self.elements.len()];
 
-		if capacity <= self.capacity() {
-			return;
+		for &index in &self.quarantine {
+			quarantined[index.try_into_unchecked()] = true;
 		}
 
-		let mut elements = core::mem::take(&mut self.elements).into_vec();
+		let mut next =
+			Key::Index::try_from_checked(self.elements.len()).unwrap_or_else(|| unreachable!());
 
-		elements.reserve_exact(capacity - elements.len());
+		for (index, element) in self.elements.iter_mut().enumerate().rev() {
+			if quarantined[index] {
+				continue;
+			}
 
-		for index in elements.len()..elements.capacity() {
-			if let Some(next) = Key::Index::try_from_checked(index + 1) {
-				elements.push(Element::Vacant {
-					version: Key::Version::MIN,
-					next,
-				});
-			} else {
-				break;
+			if let Element::Vacant { next: link, .. } = element {
+				*link = next;
+				next = Key::Index::try_from_checked(index).unwrap_or_else(|| unreachable!());
 			}
 		}
 
-		self.elements = elements.into();
+		self.next = next;
 	}
 
-	/// Reserves capacity for `additional` more elements to be inserted. Less elements
-	/// may be inserted if a `Key::Index` cannot represent the new capacity.
-	pub fn reserve(&mut self, additional: usize) {
-		let capacity = Key::Index::MAX
-			.try_into_unchecked()
-			.min(additional + self.len());
-
-		if capacity <= self.capacity() {
-			return;
+	/// Raises the highest occupied index to `index` if it is above it.
+	pub(crate) fn grow_max_occupied(&mut self, index: Key::Index) {
+		if self
+			.max_occupied
+			.is_none_or(|max| max.try_into_unchecked() < index.try_into_unchecked())
+		{
+			self.max_occupied = Some(index);
 		}
+	}
 
-		let mut elements = core::mem::take(&mut self.elements).into_vec();
+	/// Lowers the highest occupied index past any slots that are no longer occupied.
+	/// This walks the vacant slots below the old highest index, so it is only used
+	/// by operations that visit every slot anyway.
+	pub(crate) fn shrink_max_occupied(&mut self) {
+		let end = self.occupied_end();
 
-		elements.reserve(capacity - elements.len());
+		self.max_occupied = self.elements[..end]
+			.iter()
+			.rposition(|element| matches!(element, Element::Occupied { .. }))
+			.map(|index| Key::Index::try_from_checked(index).unwrap_or_else(|| unreachable!()));
+	}
 
-		for index in elements.len()..elements.capacity() {
-			if let Some(next) = Key::Index::try_from_checked(index + 1) {
-				elements.push(Element::Vacant {
-					version: Key::Version::MIN,
-					next,
-				});
-			} else {
-				break;
-			}
+	/// Keeps the highest occupied index as an upper bound after a value is removed,
+	/// only forgetting it once the [`Arena`] is empty. Reverse iteration skips the
+	/// vacant slots below the bound, so removing stays `O(1)`.
+	fn loosen_max_occupied(&mut self) {
+		if self.is_empty() {
+			self.max_occupied = None;
 		}
+	}
 
-		self.elements = elements.into();
+	/// Returns the index one past the highest occupied slot, or past a slot that
+	/// was occupied before a removal, as the bound is not lowered eagerly.
+	pub(crate) fn occupied_end(&self) -> usize {
+		self.max_occupied
+			.map_or(0, |max| max.try_into_unchecked() + 1)
 	}
 
-	/// Attempts to insert a value into the [`Arena`], returning the key if successful.
+	/// Sets how many slots freed by removals, such as [`Arena::try_remove`],
+	/// [`Arena::retain`], or [`Arena::clear`], are held back before becoming
+	/// reusable. Freed slots pass through a queue of `count` entries,
+	/// so a slot is only reused after `count` more removals, which makes keys
+	/// that are used after their value was removed easier to notice.
+	///
+	/// Lowering the count releases the oldest quarantined slots right away.
+	/// Operations that move values into free slots, such as
+	/// [`Arena::shrink_and_compact`], release every quarantined slot.
 	#[inline]
-	#[must_use]
-	pub fn try_insert(&mut self, value: Value) -> Option<Key> {
-		self.reserve(1);
+	pub fn set_quarantine(&mut self, count: usize) {
+		self.quarantine_limit = count;
 
-		if self.len() == self.capacity() {
-			return None;
+		while self.quarantine.len() > count {
+			self.release_quarantined();
 		}
+	}
 
-		let len = try_transform(self.len, |len| len.checked_add(1))?;
-		let (version, next) = self.elements[self.next.try_into_unchecked()].set(value);
+	/// Returns the number of freed slots held back from reuse.
+	#[inline]
+	#[must_use]
+	pub fn quarantined(&self) -> usize {
+		self.quarantine.len()
+	}
 
-		let key = Key::new(self.next, version);
+	/// Moves the oldest quarantined slot to the front of the free list.
+	fn release_quarantined(&mut self) {
+		let Some(index) = self.quarantine.pop_front() else {
+			return;
+		};
 
-		self.len = len;
-		self.next = next;
+		let Element::Vacant { next, .. } = &mut self.elements[index.try_into_unchecked()] else {
+			unreachable!("quarantined slots should be vacant")
+		};
 
-		Some(key)
+		*next = self.next;
+		self.next = index;
 	}
 
-	/// Inserts a value into the [`Arena`], returning the key.
-	///
-	/// # Panics
+	/// Makes a slot that was just made vacant reusable, passing it through the
+	/// quarantine first if one is set. Every removal frees its slot through here.
+	fn free_slot(&mut self, index: Key::Index) {
+		if self.quarantine_limit == 0 {
+			self.next = index;
+		} else {
+			self.quarantine.push_back(index);
+
+			if self.quarantine.len() > self.quarantine_limit {
+				self.release_quarantined();
+			}
+		}
+	}
+
+	/// Marks the [`Arena`] as read-only or writable. While read-only, any method
+	/// that changes the values or slots, such as [`Arena::get_mut`], inserting,
+	/// removing, retaining, or reserving, panics in debug builds.
 	///
-	/// Panics if the [`Arena`] is at capacity.
+	/// The flag and its checks are compiled out in release builds, where this
+	/// does nothing.
 	#[inline]
-	#[must_use]
-	pub fn insert(&mut self, value: Value) -> Key {
-		self.try_insert(value).expect("should be able to insert")
+	#[cfg_attr(not(debug_assertions), allow(unused_variables))]
+	pub fn set_read_only(&mut self, read_only: bool) {
+		#[cfg(debug_assertions)]
+		{
+			self.read_only = read_only;
+		}
 	}
 
-	/// Attempts to remove a key from the [`Arena`], returning the value if successful.
+	/// Returns `true` if the [`Arena`] is marked as read-only. Always returns
+	/// `false` in release builds, which do not keep the flag.
 	#[inline]
 	#[must_use]
-	pub fn try_remove(&mut self, key: Key) -> Option<Value> {
-		let len = try_transform(self.len, |len| len.checked_sub(1))?;
-		let value = self
-			.elements
-			.get_mut(key.index().try_into_unchecked())
-			.and_then(|element| element.reset(self.next))?;
+	pub const fn is_read_only(&self) -> bool {
+		#[cfg(debug_assertions)]
+		let read_only = self.read_only;
+		#[cfg(not(debug_assertions))]
+		let read_only = false;
 
-		self.len = len;
-		self.next = key.index();
+		read_only
+	}
 
-		Some(value)
+	#[inline]
+	pub(crate) fn assert_writable(&self) {
+		#[cfg(debug_assertions)]
+		assert!(
+			!self.read_only,
+			"`Arena` should not be mutated while read-only"
+		);
 	}
 
-	/// Removes a key from the [`Arena`], returning the value.
-	///
-	/// # Panics
+	/// Sets a callback called with the old and new capacity whenever the
+	/// storage of the [`Arena`] grows, after the values have been moved.
+	/// Clones of the [`Arena`] do not keep the callback.
+	#[inline]
+	pub fn set_realloc_hook(&mut self, hook: impl FnMut(usize, usize) + Send + Sync + 'static) {
+		self.realloc_hook = ReallocHook(Some(Box::new(hook)));
+	}
+
+	/// Returns a counter that changes whenever an element is inserted or removed,
+	/// or the [`Arena`] grows. It does not change when values are accessed or
+	/// modified in place, so it can tell whether cached lookups are still valid.
+	#[inline]
+	#[must_use]
+	pub const fn structure_epoch(&self) -> u64 {
+		self.epoch
+	}
+
+	/// Returns the number of slots of the [`Arena`], which is how many elements it
+	/// can hold without reallocating. Any spare room in the backing storage is
+	/// always filled with vacant slots, so this is never less than what was
+	/// reserved and does not shrink when inserting. Tombstones and quarantined
+	/// slots take up a slot each, and [`Arena::reserve`] accounts for them.
+	#[inline]
+	#[must_use]
+	pub const fn capacity(&self) -> usize {
+		self.elements.len()
+	}
+
+	/// Returns the number of bytes used by the backing storage of the [`Arena`].
+	/// This does not include memory owned by the values themselves.
 	///
-	/// Panics if the key is not present in the [`Arena`].
+	/// Slots always store a version and a free list link, so this is not zero
+	/// even when `Value` is zero-sized.
 	#[inline]
-	pub fn remove(&mut self, key: Key) -> Value {
-		self.try_remove(key).expect("should be able to remove")
+	#[must_use]
+	pub const fn memory_usage(&self) -> usize {
+		self.elements.capacity() * core::mem::size_of::<Element<Key::Version, Key::Index, Value>>()
 	}
 
-	/// Clears the [`Arena`], removing all values.
+	/// Returns the number of elements in the [`Arena`].
 	#[inline]
-	pub fn clear(&mut self) {
-		self.retain(|_, _| false);
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.len.try_into_unchecked()
 	}
 
-	/// Retains only the elements specified by the predicate.
+	/// Returns `true` if the [`Arena`] contains no elements.
 	#[inline]
-	pub fn retain(&mut self, mut f: impl FnMut(Key, &Value) -> bool) {
-		for (index, element) in self.elements.iter_mut().enumerate() {
-			if self.len.try_into_unchecked() == Key::Index::MIN.try_into_unchecked() {
-				break;
-			}
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.len.try_into_unchecked() == Key::Index::MIN.try_into_unchecked()
+	}
 
-			if let Element::Occupied { version, value } = element {
-				let index = Key::Index::try_from_checked(index).unwrap_or_else(|| unreachable!());
-				let key = Key::new(index, *version);
+	/// Returns `true` if no more values can be inserted, because every slot a
+	/// `Key::Index` can address is in use.
+	#[inline]
+	#[must_use]
+	pub fn is_full(&self) -> bool {
+		let slots = self.elements.len();
 
-				if !f(key, value) {
-					let len = try_transform(self.len, |len| len.checked_sub(1))
-						.unwrap_or_else(|| unreachable!());
+		self.next.try_into_unchecked() == slots && slots >= Key::Index::MAX.try_into_unchecked()
+	}
+
+	/// Returns a reference to the value corresponding to the given key.
+	///
+	/// Only keys of the exact `Key` type are accepted, so keys with a different
+	/// `Index` or `Version` type are rejected at compile time.
+	///
+	/// ```compile_fail
+	/// # use core::num::NonZeroU32;
+	/// # use arena::{collection::Arena, referent::{Id, Nil}};
+	/// let arena = Arena::<Id<u32, NonZeroU32>, u32>::new();
+	/// let key = Id::<u32, Nil>::dangling();
+	///
+	/// let _ = arena.get(key);
+	/// ```
+	#[inline]
+	#[must_use]
+	pub fn get(&self, key: Key) -> Option<&Value> {
+		let element = self.elements.get(key.index().try_into_unchecked());
 
-					element.reset(self.next).unwrap_or_else(|| unreachable!());
+		#[cfg(feature = "stats")]
+		self.stats.record(element, key.version());
 
-					self.next = index;
-					self.len = len;
-				}
-			}
+		element.and_then(|element| element.get(key.version()))
+	}
+
+	/// Creates a key for the slot at `index`, or `None` if the slot does not exist.
+	/// The slot does not need to be occupied, so the key may not resolve to a value.
+	#[inline]
+	#[must_use]
+	pub fn make_key(&self, index: usize, version: Key::Version) -> Option<Key> {
+		if index >= self.capacity() {
+			return None;
 		}
+
+		Key::Index::try_from_checked(index).map(|index| Key::new(index, version))
 	}
-}
 
-impl<Key: Referent, Value> Index<Key> for Arena<Key, Value> {
-	type Output = Value;
+	/// Returns the current key and value of the slot addressed by `key`, if it is
+	/// occupied. Unlike [`Arena::get`], the version of `key` is ignored, so an
+	/// outdated key can be used to recover the key of whichever value now holds
+	/// its slot.
+	#[inline]
+	#[must_use]
+	pub fn get_key_value(&self, key: Key) -> Option<(Key, &Value)> {
+		let element = self.elements.get(key.index().try_into_unchecked())?;
 
+		element
+			.as_ref()
+			.map(|value| (Key::new(key.index(), element.version()), value))
+	}
+
+	/// Returns an iterator pairing each of the given keys with its value, in the
+	/// order of the keys, with `None` for keys that are not present.
 	#[inline]
-	fn index(&self, key: Key) -> &Self::Output {
-		self.get(key).expect("should be able to get")
+	pub fn get_all<'a, I>(&'a self, keys: I) -> impl Iterator<Item = (Key, Option<&'a Value>)> + 'a
+	where
+		I: IntoIterator<Item = Key>,
+		I::IntoIter: 'a,
+	{
+		keys.into_iter().map(|key| (key, self.get(key)))
 	}
-}
 
-impl<Key: Referent, Value> IndexMut<Key> for Arena<Key, Value> {
+	/// Returns `true` if the [`Arena`] contains a value for the given key.
 	#[inline]
-	fn index_mut(&mut self, key: Key) -> &mut Self::Output {
-		self.get_mut(key).expect("should be able to get_mut")
+	#[must_use]
+	pub fn contains_key(&self, key: Key) -> bool {
+		self.elements
+			.get(key.index().try_into_unchecked())
+			.is_some_and(|element| element.get(key.version()).is_some())
 	}
-}
 
-impl<Key: Referent + Debug, Value: Debug> Debug for Arena<Key, Value> {
-	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-		f.debug_map().entries(self.iter()).finish()
+	/// Returns the position of the slot of the given key, if the key is present.
+	/// It stays the same for as long as the key is present, so it can be used to
+	/// index storage kept alongside the [`Arena`].
+	#[inline]
+	#[must_use]
+	pub fn stable_index(&self, key: Key) -> Option<usize> {
+		self.contains_key(key)
+			.then(|| key.index().try_into_unchecked())
 	}
-}
 
-#[cfg(test)]
-mod test {
-	use crate::{
-		collection::Arena,
-		referent::{Id, Nil},
-	};
+	/// Returns a [`Handle`] to the value of the given key, which borrows the
+	/// [`Arena`] and so can not outlive it or see it change.
+	#[inline]
+	#[must_use]
+	pub fn handle(&self, key: Key) -> Option<Handle<'_, Key, Value>> {
+		let value = self.get(key)?;
+
+		Some(Handle { key, value })
+	}
+
+	/// Returns the `n` keys and values with the highest score given by `f`, best
+	/// first, with ties going to the lowest index. Only `n` entries are kept
+	/// while scanning, so this is cheaper than sorting the whole [`Arena`].
+	#[must_use]
+	pub fn top_n_by<B: Ord>(&self, n: usize, mut f: impl FnMut(&Value) -> B) -> Vec<(Key, &Value)> {
+		if n == 0 {
+			return Vec::new();
+		}
+
+		let mut heap = BinaryHeap::with_capacity(n.min(self.len()) + 1);
+
+		// The heap keeps the worst entry on top, so it is the one pushed out.
+		for (key, value) in self.iter() {
+			heap.push(Reverse((
+				f(value),
+				Reverse(key.index().try_into_unchecked()),
+			)));
+
+			if heap.len() > n {
+				heap.pop();
+			}
+		}
+
+		heap.into_sorted_vec()
+			.into_iter()
+			.map(|Reverse((_, Reverse(index)))| {
+				let element = &self.elements[index];
+				let index = Key::Index::try_from_checked(index).unwrap_or_else(|| unreachable!());
+				let value = element.as_ref().unwrap_or_else(|| unreachable!());
+
+				(Key::new(index, element.version()), value)
+			})
+			.collect()
+	}
+
+	/// Returns the XOR of all live keys, each packed into a `u64` with its version
+	/// in the high half, as a cheap checksum of the key set independent of order.
+	#[must_use]
+	pub fn keys_checksum(&self) -> u64 {
+		self.keys().fold(0, |checksum, key| {
+			let index = key.index().try_into_unchecked() as u64;
+			let version = key.version().try_into_unchecked().get();
+
+			checksum ^ index ^ version.rotate_left(32)
+		})
+	}
+
+	/// Returns a mutable reference to the value corresponding to the given key.
+	#[inline]
+	#[must_use]
+	pub fn get_mut(&mut self, key: Key) -> Option<&mut Value> {
+		self.assert_writable();
+
+		let element = self.elements.get_mut(key.index().try_into_unchecked());
+
+		#[cfg(feature = "stats")]
+		self.stats.record(element.as_deref(), key.version());
+
+		element.and_then(|element| element.get_mut(key.version()))
+	}
+
+	/// Calls `f` with a mutable reference to the value of the given key, returning
+	/// its result, or `None` without calling `f` if the key is not present. The
+	/// borrow ends when `f` returns, so it can not be held across an `.await`.
+	#[inline]
+	pub fn with_mut<R>(&mut self, key: Key, f: impl FnOnce(&mut Value) -> R) -> Option<R> {
+		self.get_mut(key).map(f)
+	}
+
+	/// Returns mutable references to the values of all the given keys at once,
+	/// or `None` if any key is not present or two keys share an index.
+	#[must_use]
+	pub fn get_disjoint_mut<const N: usize>(&mut self, keys: [Key; N]) -> Option<[&mut Value; N]> {
+		self.assert_writable();
+
+		let indices = keys.map(|key| key.index().try_into_unchecked());
+		let mut order: [usize; N] = core::array::from_fn(|position| position);
+
+		order.sort_unstable_by_key(|&position| indices[position]);
+
+		if order
+			.windows(2)
+			.any(|pair| indices[pair[0]] == indices[pair[1]])
+		{
+			return None;
+		}
+
+		let mut values: [Option<&mut Value>; N] = core::array::from_fn(|_| None);
+		let mut rest = self.elements.as_mut_slice();
+		let mut start = 0;
+
+		for position in order {
+			let index = indices[position];
+			let (element, tail) = core::mem::take(&mut rest)
+				.get_mut(index - start..)?
+				.split_first_mut()?;
+
+			values[position] = Some(element.get_mut(keys[position].version())?);
+			rest = tail;
+			start = index + 1;
+		}
+
+		Some(values.map(|value| value.unwrap_or_else(|| unreachable!())))
+	}
+
+	/// Swaps the values of two keys, leaving the keys themselves valid. Returns
+	/// `false` without changing anything if either key is not present. Swapping
+	/// a key with itself does nothing and returns `true`.
+	pub fn swap(&mut self, a: Key, b: Key) -> bool {
+		if a.index().try_into_unchecked() == b.index().try_into_unchecked() {
+			return self.contains_key(a) && self.contains_key(b);
+		}
+
+		self.get_disjoint_mut([a, b])
+			.map(|[a, b]| core::mem::swap(a, b))
+			.is_some()
+	}
+
+	/// Calls `f` on the values of the given keys in ascending index order.
+	/// Duplicate keys are visited once and keys not present in the [`Arena`]
+	/// are skipped.
+	pub fn for_each_mut_sorted(&mut self, keys: &[Key], mut f: impl FnMut(Key, &mut Value)) {
+		let mut keys: Vec<Key> = keys
+			.iter()
+			.copied()
+			.filter(|&key| self.get(key).is_some())
+			.collect();
+
+		keys.sort_unstable_by_key(|key| key.index().try_into_unchecked());
+		keys.dedup_by_key(|key| key.index().try_into_unchecked());
+
+		for key in keys {
+			let value = self.get_mut(key).unwrap_or_else(|| unreachable!());
+
+			f(key, value);
+		}
+	}
+
+	/// Calls `f` on every key and value of the [`Arena`] in a single pass.
+	pub fn for_each_mut(&mut self, mut f: impl FnMut(Key, &mut Value)) {
+		self.assert_writable();
+
+		for (index, element) in self.elements.iter_mut().enumerate() {
+			if let Element::Occupied { version, value } = element {
+				let index = Key::Index::try_from_checked(index).unwrap_or_else(|| unreachable!());
+
+				f(Key::new(index, *version), value);
+			}
+		}
+	}
+
+	/// Returns the key and value of the element with the maximum value of `f`.
+	/// On ties, the element with the lowest index is returned.
+	#[inline]
+	#[must_use]
+	pub fn max_by_key<B: Ord>(&self, mut f: impl FnMut(&Value) -> B) -> Option<(Key, &Value)> {
+		self.iter().rev().max_by_key(|entry| f(entry.1))
+	}
+
+	/// Returns the key and value of the element with the minimum value of `f`.
+	/// On ties, the element with the lowest index is returned.
+	#[inline]
+	#[must_use]
+	pub fn min_by_key<B: Ord>(&self, mut f: impl FnMut(&Value) -> B) -> Option<(Key, &Value)> {
+		self.iter().min_by_key(|entry| f(entry.1))
+	}
+
+	/// Returns mutable references to all values, split in two by whether
+	/// `pred` returns `true` for their key.
+	#[must_use]
+	pub fn partition_values_mut(
+		&mut self,
+		mut pred: impl FnMut(Key) -> bool,
+	) -> (Vec<&mut Value>, Vec<&mut Value>) {
+		let mut matching = Vec::new();
+		let mut rest = Vec::new();
+
+		for (key, value) in self.iter_mut() {
+			if pred(key) {
+				matching.push(value);
+			} else {
+				rest.push(value);
+			}
+		}
+
+		(matching, rest)
+	}
+
+	/// Returns the indices and values of the occupied slots as two parallel
+	/// vectors, in ascending index order.
+	#[must_use]
+	pub fn export_dense(&self) -> (Vec<Key::Index>, Vec<&Value>) {
+		self.iter().map(|(key, value)| (key.index(), value)).unzip()
+	}
+
+	/// Returns a [`BTreeMap`] with a copy of every key and value in the [`Arena`].
+	///
+	/// Keys are visited in ascending index order, so building the map does
+	/// no extra sorting work for key types ordered by index, such as [`Id`].
+	///
+	/// [`Id`]: crate::referent::Id
+	#[must_use]
+	pub fn to_btree_map(&self) -> BTreeMap<Key, Value>
+	where
+		Key: Ord,
+		Value: Clone,
+	{
+		self.iter()
+			.map(|(key, value)| (key, value.clone()))
+			.collect()
+	}
+
+	/// Converts the [`Arena`] into a [`BTreeMap`] of its keys and values.
+	///
+	/// See [`Arena::to_btree_map`] for details.
+	#[must_use]
+	pub fn into_btree_map(self) -> BTreeMap<Key, Value>
+	where
+		Key: Ord,
+	{
+		self.into_iter().collect()
+	}
+
+	/// Returns the number of indices needed to store the all the elements in the [`Arena`]
+	#[inline]
+	#[must_use]
+	pub fn indices_needed(&self) -> usize {
+		self.keys()
+			.next_back()
+			.map_or(0, |id| id.index().try_into_unchecked() + 1)
+	}
+
+	/// Fills the spare capacity of the backing storage with vacant slots, each linking
+	/// to the next. Only slots whose successor index is representable are added, so
+	/// that the free list can always end at the last slot plus one.
+	pub(crate) fn fill_vacant(&mut self) {
+		let start = self.elements.len();
+		let end = self
+			.elements
+			.capacity()
+			.min(Key::Index::MAX.try_into_unchecked());
+
+		self.elements
+			.extend((start..end).map(|index| Element::Vacant {
+				version: self.version_floor,
+				next: Key::Index::try_from_checked(index + 1).unwrap_or_else(|| unreachable!()),
+			}));
+	}
+
+	/// Raises the version new slots start at to that of every slot from `end`
+	/// onwards, before they are truncated. A vacant slot already holds the version
+	/// its next key will use, so keys handed out before the truncation do not
+	/// resolve to values in the same slots once the [`Arena`] grows again.
+	fn raise_version_floor(&mut self, end: usize) {
+		for element in &self.elements[end..] {
+			let version = element.version();
+
+			if version.try_into_unchecked() > self.version_floor.try_into_unchecked() {
+				self.version_floor = version;
+			}
+		}
+	}
+
+	/// Returns the number of slots that insertions can not use, which are those
+	/// holding values or tombstones and those in quarantine.
+	fn used_slots(&self) -> usize {
+		self.len() + self.tombstones + self.quarantine.len()
+	}
+
+	/// Reserves capacity for `additional` more elements to be inserted. Less elements
+	/// may be inserted if a `Key::Index` cannot represent the new capacity.
+	pub fn reserve_exact(&mut self, additional: usize) {
+		self.assert_writable();
+
+		let capacity = Key::Index::MAX
+			.try_into_unchecked()
+			.min(additional + self.used_slots());
+
+		if capacity <= self.capacity() {
+			return;
+		}
+
+		self.epoch = self.epoch.wrapping_add(1);
+
+		let old_capacity = self.capacity();
+
+		self.elements.reserve_exact(capacity - self.elements.len());
+		self.fill_vacant();
+		self.realloc_hook.call(old_capacity, self.capacity());
+	}
+
+	/// Reserves capacity for `additional` more elements to be inserted. Less elements
+	/// may be inserted if a `Key::Index` cannot represent the new capacity.
+	pub fn reserve(&mut self, additional: usize) {
+		self.assert_writable();
+
+		let capacity = Key::Index::MAX
+			.try_into_unchecked()
+			.min(additional + self.used_slots());
+
+		if capacity <= self.capacity() {
+			return;
+		}
+
+		self.epoch = self.epoch.wrapping_add(1);
+
+		let old_capacity = self.capacity();
+		let capacity = capacity.max(MIN_NON_ZERO_CAPACITY);
+
+		self.elements.reserve(capacity - self.elements.len());
+		self.fill_vacant();
+		self.realloc_hook.call(old_capacity, self.capacity());
+	}
+
+	/// Tries to reserve capacity for `additional` more elements to be inserted, like
+	/// [`Arena::reserve_exact`]. The [`Arena`] is left unchanged if allocating fails.
+	///
+	/// # Errors
+	///
+	/// Returns an error if the capacity overflows or the allocator reports a failure.
+	pub fn try_reserve_exact(&mut self, additional: usize) -> Result<(), TryReserveError> {
+		self.assert_writable();
+
+		let capacity = Key::Index::MAX
+			.try_into_unchecked()
+			.min(additional.saturating_add(self.used_slots()));
+
+		if capacity <= self.capacity() {
+			return Ok(());
+		}
+
+		let old_capacity = self.capacity();
+
+		self.elements
+			.try_reserve_exact(capacity - self.elements.len())
+			.map_err(TryReserveError)?;
+
+		self.epoch = self.epoch.wrapping_add(1);
+		self.fill_vacant();
+		self.realloc_hook.call(old_capacity, self.capacity());
+
+		Ok(())
+	}
+
+	/// Tries to reserve capacity for `additional` more elements to be inserted, like
+	/// [`Arena::reserve`]. The [`Arena`] is left unchanged if allocating fails.
+	///
+	/// # Errors
+	///
+	/// Returns an error if the capacity overflows or the allocator reports a failure.
+	pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+		self.assert_writable();
+
+		let capacity = Key::Index::MAX
+			.try_into_unchecked()
+			.min(additional.saturating_add(self.used_slots()));
+
+		if capacity <= self.capacity() {
+			return Ok(());
+		}
+
+		let old_capacity = self.capacity();
+		let capacity = capacity.max(MIN_NON_ZERO_CAPACITY);
+
+		self.elements
+			.try_reserve(capacity - self.elements.len())
+			.map_err(TryReserveError)?;
+
+		self.epoch = self.epoch.wrapping_add(1);
+		self.fill_vacant();
+		self.realloc_hook.call(old_capacity, self.capacity());
+
+		Ok(())
+	}
+
+	/// Attempts to insert a value into the [`Arena`], returning the key if successful.
+	///
+	/// The key returned depends only on the sequence of operations performed on the
+	/// [`Arena`] so far, and not on its capacity. Two arenas that undergo the same
+	/// insertions and removals will hand out the same keys.
+	///
+	/// # Panics
+	///
+	/// Panics if the free list points at a slot that is not vacant, which can
+	/// only happen through a bug in the [`Arena`] itself.
+	#[inline]
+	#[must_use]
+	pub fn try_insert(&mut self, value: Value) -> Option<Key> {
+		let key = self.vacant_key()?;
+
+		self.occupy(value);
+
+		Some(key)
+	}
+
+	/// Returns the key the next insertion will use, growing the [`Arena`] if needed.
+	fn vacant_key(&mut self) -> Option<Key> {
+		self.assert_writable();
+
+		if self.next.try_into_unchecked() == self.elements.len() {
+			self.reserve(1);
+		}
+
+		self.free_key()
+	}
+
+	/// Returns the key the next insertion will use, if a slot is free without
+	/// growing the [`Arena`].
+	fn free_key(&self) -> Option<Key> {
+		if self.next.try_into_unchecked() == self.elements.len() {
+			return None;
+		}
+
+		try_transform(self.len, |len| len.checked_add(1))?;
+
+		let index = self.next.try_into_unchecked();
+		let Element::Vacant { version, .. } = self.elements[index] else {
+			panic!("free list corrupted: next points at occupied slot {index}");
+		};
+
+		Some(Key::new(self.next, version))
+	}
+
+	/// Fills the slot of the key returned by [`Arena::vacant_key`].
+	fn occupy(&mut self, value: Value) {
+		let len =
+			try_transform(self.len, |len| len.checked_add(1)).unwrap_or_else(|| unreachable!());
+		let (_, next) = self.elements[self.next.try_into_unchecked()].set(value);
+
+		self.grow_max_occupied(self.next);
+		self.len = len;
+		self.next = next;
+		self.epoch = self.epoch.wrapping_add(1);
+	}
+
+	/// Reserves the slot the next insertion will use, returning a handle to fill it
+	/// with a value later. The key is known before the value is provided.
+	#[inline]
+	#[must_use]
+	pub fn reserve_key(&mut self) -> Option<PendingKey<'_, Key, Value>> {
+		let key = self.vacant_key()?;
+
+		Some(PendingKey { arena: self, key })
+	}
+
+	/// Attempts to insert a value into the [`Arena`] without reallocating,
+	/// returning the key if successful. The value is given back if no slot is
+	/// free or `Key::Index` can not represent another one.
+	///
+	/// # Errors
+	///
+	/// Returns the value if the [`Arena`] is full.
+	#[inline]
+	pub fn insert_within_capacity(&mut self, value: Value) -> Result<Key, Value> {
+		self.assert_writable();
+
+		let Some(key) = self.free_key() else {
+			return Err(value);
+		};
+
+		self.occupy(value);
+
+		Ok(key)
+	}
+
+	/// Inserts a value into the [`Arena`], returning the key.
+	///
+	/// # Panics
+	///
+	/// Panics if the [`Arena`] is at capacity.
+	#[inline]
+	#[must_use]
+	pub fn insert(&mut self, value: Value) -> Key {
+		self.try_insert(value).expect("should be able to insert")
+	}
+
+	/// Inserts a value into the [`Arena`], returning the key. Two arenas that
+	/// undergo the same sequence of operations hand out the same keys, whatever
+	/// their capacity, so this can be relied on for deterministic replay.
+	///
+	/// This is the same as [`Arena::insert`], which also upholds the guarantee,
+	/// but names it at call sites that depend on it.
+	///
+	/// # Panics
+	///
+	/// Panics if the [`Arena`] is at capacity.
+	#[inline]
+	#[must_use]
+	pub fn insert_deterministic(&mut self, value: Value) -> Key {
+		self.insert(value)
+	}
+
+	/// Attempts to insert the value returned by `f` into the [`Arena`], returning the
+	/// key if successful. The key is passed to `f`, so the value can refer to itself.
+	///
+	/// `f` is not called if the [`Arena`] is at capacity. If `f` panics, the
+	/// [`Arena`] is left as it was.
+	#[inline]
+	#[must_use]
+	pub fn try_insert_with_key(&mut self, f: impl FnOnce(Key) -> Value) -> Option<Key> {
+		let key = self.vacant_key()?;
+
+		self.occupy(f(key));
+
+		Some(key)
+	}
+
+	/// Inserts the value returned by `f` into the [`Arena`], returning the key.
+	/// The key is passed to `f`, so the value can refer to itself.
+	///
+	/// # Panics
+	///
+	/// Panics if the [`Arena`] is at capacity.
+	#[inline]
+	#[must_use]
+	pub fn insert_with_key(&mut self, f: impl FnOnce(Key) -> Value) -> Key {
+		self.try_insert_with_key(f)
+			.expect("should be able to insert")
+	}
+
+	/// Attempts to remove a key from the [`Arena`], returning the value if successful.
+	#[inline]
+	#[must_use]
+	pub fn try_remove(&mut self, key: Key) -> Option<Value> {
+		self.assert_writable();
+
+		let len = try_transform(self.len, |len| len.checked_sub(1))?;
+
+		// Only the exact key may remove the value, so that stale keys can not
+		// remove a newer value and vacant slots are never reset twice.
+		let value = self
+			.elements
+			.get_mut(key.index().try_into_unchecked())
+			.filter(|element| element.get(key.version()).is_some())
+			.and_then(|element| element.reset(self.next))?;
+
+		self.len = len;
+		self.epoch = self.epoch.wrapping_add(1);
+		self.loosen_max_occupied();
+		self.free_slot(key.index());
+
+		Some(value)
+	}
+
+	/// Attempts to remove a key from the [`Arena`], returning the value and the new
+	/// version of its slot if successful.
+	#[inline]
+	#[must_use]
+	pub fn remove_versioned(&mut self, key: Key) -> Option<(Value, Key::Version)> {
+		let value = self.try_remove(key)?;
+		let version = self.elements[key.index().try_into_unchecked()].version();
+
+		Some((value, version))
+	}
+
+	/// Removes a key from the [`Arena`], returning the value.
+	///
+	/// # Panics
+	///
+	/// Panics if the key is not present in the [`Arena`].
+	#[inline]
+	pub fn remove(&mut self, key: Key) -> Value {
+		self.try_remove(key).expect("should be able to remove")
+	}
+
+	/// Removes a key from the [`Arena`], keeping its value readable through
+	/// [`Arena::get_tombstone`] until [`Arena::purge_tombstones`] is called.
+	/// The slot is not reused until then.
+	#[inline]
+	pub fn remove_tombstoned(&mut self, key: Key) -> Option<&Value> {
+		self.assert_writable();
+		self.get(key)?;
+
+		let len = try_transform(self.len, |len| len.checked_sub(1))?;
+		let index = key.index().try_into_unchecked();
+
+		if !self.elements[index].entomb() {
+			return None;
+		}
+
+		self.len = len;
+		self.tombstones += 1;
+		self.epoch = self.epoch.wrapping_add(1);
+		self.loosen_max_occupied();
+
+		self.elements[index].tombstone(key.version())
+	}
+
+	/// Returns a reference to the value of a key removed by [`Arena::remove_tombstoned`],
+	/// if it has not been purged yet.
+	#[inline]
+	#[must_use]
+	pub fn get_tombstone(&self, key: Key) -> Option<&Value> {
+		self.elements
+			.get(key.index().try_into_unchecked())
+			.and_then(|element| element.tombstone(key.version()))
+	}
+
+	/// Drops the values of all tombstones and makes their slots available again.
+	pub fn purge_tombstones(&mut self) {
+		self.assert_writable();
+
+		for index in 0..self.elements.len() {
+			if self.elements[index].purge(self.next).is_some() {
+				let index = Key::Index::try_from_checked(index).unwrap_or_else(|| unreachable!());
+
+				self.free_slot(index);
+				self.tombstones -= 1;
+				self.epoch = self.epoch.wrapping_add(1);
+			}
+		}
+	}
+
+	/// Returns a copy of the [`Arena`] with its values sorted into the lowest
+	/// indices and all versions reset. Arenas holding the same values produce
+	/// equal copies, regardless of which slots held them or how they were built.
+	///
+	/// The values are sorted because the slots that held them depend on the order
+	/// of past insertions and removals, so only an ordering of the values themselves
+	/// can place equal sets of values the same way. Values without an [`Ord`]
+	/// implementation, such as floats, can use [`Arena::canonicalize_by`].
+	///
+	/// Keys of the original [`Arena`] should not be used with the copy.
+	#[must_use]
+	pub fn canonicalize(&self) -> Self
+	where
+		Value: Clone + Ord,
+	{
+		self.canonicalize_by(Ord::cmp)
+	}
+
+	/// Returns a canonical copy of the [`Arena`] like [`Arena::canonicalize`], with
+	/// the values sorted by `compare`. Values that compare equal should be equal,
+	/// or they may be placed differently in copies of otherwise equal arenas.
+	///
+	/// Keys of the original [`Arena`] should not be used with the copy.
+	#[must_use]
+	pub fn canonicalize_by(&self, mut compare: impl FnMut(&Value, &Value) -> Ordering) -> Self
+	where
+		Value: Clone,
+	{
+		let mut values: Vec<_> = self.values().collect();
+		let mut arena = Self::with_capacity(values.len());
+
+		values.sort_unstable_by(|a, b| compare(a, b));
+
+		for value in values {
+			let _key = arena.insert(value.clone());
+		}
+
+		arena
+	}
+
+	/// Moves every value down into the lowest free slots and truncates the
+	/// backing storage to fit, returning the old and new key of every value
+	/// that was moved. Keys of values that were not moved stay valid.
+	///
+	/// Tombstones are purged first. Slots whose version can not be bumped any
+	/// further are never freed, so their values are left in place. Quarantined
+	/// slots may be filled, so the quarantine is released.
+	///
+	/// Keys not in the returned mapping, other than those of unmoved values,
+	/// should not be used afterwards. They do not resolve to values inserted
+	/// once the [`Arena`] grows again, as new slots start past the versions of
+	/// the slots cut off by the truncation.
+	pub fn shrink_and_compact(&mut self) -> Vec<(Key, Key)> {
+		self.assert_writable();
+		self.purge_tombstones();
+
+		let mut remap = Vec::new();
+		let mut target = 0;
+
+		for index in 0..self.elements.len() {
+			let Element::Occupied { version, .. } = self.elements[index] else {
+				continue;
+			};
+
+			let from = Key::Index::try_from_checked(index).unwrap_or_else(|| unreachable!());
+
+			if index != target {
+				if let Some(value) = self.elements[index].reset(from) {
+					let to = Key::Index::try_from_checked(target).unwrap_or_else(|| unreachable!());
+					let (new_version, _) = self.elements[target].set(value);
+
+					remap.push((Key::new(from, version), Key::new(to, new_version)));
+				} else {
+					target = index;
+				}
+			}
+
+			target += 1;
+		}
+
+		self.raise_version_floor(target);
+		self.elements.truncate(target);
+		self.elements.shrink_to_fit();
+		self.fill_vacant();
+		self.max_occupied = target.checked_sub(1).and_then(Key::Index::try_from_checked);
+		self.quarantine.clear();
+		self.rethread();
+		self.epoch = self.epoch.wrapping_add(1);
+
+		remap
+	}
+
+	/// Truncates the trailing vacant slots of the backing storage and releases the
+	/// memory they used. Occupied slots and tombstones are never removed, so every
+	/// key that resolves before shrinking still resolves afterwards.
+	///
+	/// The free list is rebuilt so that the remaining vacant slots are reused in
+	/// ascending order. Slots added when the [`Arena`] grows again start past the
+	/// versions of the slots cut off, so their old keys stay invalid.
+	#[inline]
+	pub fn shrink_to_fit(&mut self) {
+		self.shrink_to(0);
+	}
+
+	/// Truncates trailing vacant slots of the backing storage like
+	/// [`Arena::shrink_to_fit`], but keeps at least `min_capacity` slots.
+	/// Does nothing if the capacity is already at most `min_capacity`.
+	///
+	/// Quarantined slots that are kept stay quarantined, while those cut off
+	/// are dropped from the quarantine.
+	pub fn shrink_to(&mut self, min_capacity: usize) {
+		self.assert_writable();
+
+		let end = self
+			.elements
+			.iter()
+			.rposition(|element| !matches!(element, Element::Vacant { .. }))
+			.map_or(0, |index| index + 1)
+			.max(min_capacity);
+
+		if end >= self.elements.len() {
+			return;
+		}
+
+		self.shrink_max_occupied();
+		self.raise_version_floor(end);
+		self.quarantine
+			.retain(|index| index.try_into_unchecked() < end);
+		self.elements.truncate(end);
+		self.elements.shrink_to_fit();
+		self.fill_vacant();
+		self.rethread();
+		self.epoch = self.epoch.wrapping_add(1);
+	}
+
+	/// Grows or shrinks the [`Arena`] to exactly `target` slots. Shrinking only
+	/// removes trailing vacant slots, like [`Arena::shrink_to`], so keys of
+	/// removed values stay invalid when the slots are added back.
+	///
+	/// # Errors
+	///
+	/// Returns an error without changing anything if a slot past `target` is
+	/// occupied or a tombstone, or if a `Key::Index` can not address `target` slots.
+	pub fn set_capacity(&mut self, target: usize) -> Result<(), CapacityError> {
+		let required = self
+			.elements
+			.iter()
+			.rposition(|element| !matches!(element, Element::Vacant { .. }))
+			.map_or(0, |index| index + 1);
+
+		if target < required {
+			return Err(CapacityError::Occupied { required });
+		}
+
+		if target > Key::Index::MAX.try_into_unchecked() {
+			return Err(CapacityError::Unrepresentable);
+		}
+
+		// Every used slot lies below the current capacity, which is at most `target`
+		// when growing, and `reserve_exact` counts them on top of what is asked for.
+		if target < self.capacity() {
+			self.shrink_to(target);
+		} else {
+			self.reserve_exact(target - self.used_slots());
+		}
+
+		Ok(())
+	}
+
+	/// Clears the [`Arena`], removing all values.
+	///
+	/// Keys from before the clear are invalidated by bumping the version of
+	/// their slots. Keys using [`Nil`](crate::referent::Nil) versions can not
+	/// be invalidated, and may resolve to new values inserted afterwards.
+	///
+	/// Values in slots whose version is exhausted can not be removed, so they
+	/// survive the clear and their keys still resolve.
+	#[inline]
+	pub fn clear(&mut self) {
+		self.retain(|_, _| false);
+	}
+
+	/// Removes all values and tombstones, and resets every slot to the initial
+	/// version, keeping the capacity. Vacant slots are reused in ascending order,
+	/// and slots added later also start at the initial version.
+	///
+	/// Unlike [`Arena::clear`], keys from before the reset are not invalidated,
+	/// and may resolve to new values inserted afterwards.
+	pub fn reset(&mut self) {
+		self.assert_writable();
+
+		self.elements.fill_with(|| Element::Vacant {
+			version: Key::Version::MIN,
+			next: Key::Index::MIN,
+		});
+
+		self.quarantine.clear();
+		self.rethread();
+		self.len = Key::Index::MIN;
+		self.tombstones = 0;
+		self.max_occupied = None;
+		self.version_floor = Key::Version::MIN;
+		self.epoch = self.epoch.wrapping_add(1);
+	}
+
+	/// Clears the [`Arena`], passing every value removed to `sink` in ascending
+	/// index order. Keys are invalidated as with [`Arena::clear`].
+	///
+	/// Values in slots whose version is exhausted can not be removed, so they
+	/// are left in place.
+	pub fn clear_into(&mut self, mut sink: impl FnMut(Value)) {
+		self.assert_writable();
+
+		for index in 0..self.elements.len() {
+			let element = &mut self.elements[index];
+
+			if element.as_ref().is_none() || element.is_exhausted() {
+				continue;
+			}
+
+			let len =
+				try_transform(self.len, |len| len.checked_sub(1)).unwrap_or_else(|| unreachable!());
+
+			let index = Key::Index::try_from_checked(index).unwrap_or_else(|| unreachable!());
+
+			sink(element.reset(self.next).unwrap_or_else(|| unreachable!()));
+
+			self.free_slot(index);
+			self.len = len;
+			self.epoch = self.epoch.wrapping_add(1);
+		}
+
+		self.shrink_max_occupied();
+	}
+
+	/// Moves every value of `other` into the [`Arena`] in ascending index order,
+	/// returning the old key in `other` and the new key of each value moved.
+	///
+	/// Values in slots of `other` whose version is exhausted can not be removed,
+	/// so they are left in place.
+	///
+	/// # Panics
+	///
+	/// Panics if the [`Arena`] runs out of capacity.
+	pub fn append(&mut self, other: &mut Self) -> Vec<(Key, Key)> {
+		self.reserve(other.len());
+
+		other
+			.drain()
+			.map(|(key, value)| (key, self.insert(value)))
+			.collect()
+	}
+
+	/// Moves every value for which `predicate` returns `true` into a new [`Arena`]
+	/// in ascending index order, returning it along with the old key and the new
+	/// key of each value moved.
+	///
+	/// Values in slots whose version is exhausted can not be removed, so they
+	/// are left in place even if they match.
+	pub fn split_off(
+		&mut self,
+		mut predicate: impl FnMut(Key, &Value) -> bool,
+	) -> (Self, Vec<(Key, Key)>) {
+		let mut other = Self::new();
+		let remap = self
+			.extract_if(|key, value| predicate(key, value))
+			.map(|(key, value)| (key, other.insert(value)))
+			.collect();
+
+		(other, remap)
+	}
+
+	/// Removes every element in ascending index order, transforming each with
+	/// `f` and collecting the results.
+	///
+	/// Stops at the first error, which is returned. The element passed to the
+	/// failing call is consumed, but all elements not yet visited stay in the
+	/// [`Arena`] under their original keys.
+	///
+	/// Values in slots whose version is exhausted can not be removed, so they
+	/// are skipped and left in place.
+	pub fn try_drain<B, E>(
+		&mut self,
+		mut f: impl FnMut(Key, Value) -> Result<B, E>,
+	) -> Result<Vec<B>, E> {
+		self.assert_writable();
+
+		let mut drained = Vec::with_capacity(self.len());
+		let mut result = Ok(());
+
+		for index in 0..self.elements.len() {
+			let element = &mut self.elements[index];
+
+			if self.len.try_into_unchecked() == Key::Index::MIN.try_into_unchecked() {
+				break;
+			}
+
+			if element.is_exhausted() {
+				continue;
+			}
+
+			if let Element::Occupied { version, .. } = *element {
+				let index = Key::Index::try_from_checked(index).unwrap_or_else(|| unreachable!());
+				let len = try_transform(self.len, |len| len.checked_sub(1))
+					.unwrap_or_else(|| unreachable!());
+
+				let value = element.reset(self.next).unwrap_or_else(|| unreachable!());
+
+				self.free_slot(index);
+				self.len = len;
+				self.epoch = self.epoch.wrapping_add(1);
+
+				match f(Key::new(index, version), value) {
+					Ok(value) => drained.push(value),
+					Err(error) => {
+						result = Err(error);
+
+						break;
+					}
+				}
+			}
+		}
+
+		self.shrink_max_occupied();
+
+		result.map(|()| drained)
+	}
+
+	/// Retains only the elements specified by the predicate.
+	///
+	/// Values in slots whose version is exhausted can not be removed, so they
+	/// are kept, as with [`Arena::try_remove`].
+	#[inline]
+	pub fn retain(&mut self, f: impl FnMut(Key, &Value) -> bool) {
+		self.retain_notify(f, |_| {});
+	}
+
+	/// Retains only the elements specified by the predicate, which may also
+	/// modify the elements it keeps.
+	#[inline]
+	pub fn retain_mut(&mut self, f: impl FnMut(Key, &mut Value) -> bool) {
+		self.retain_with(f, |_| {});
+	}
+
+	/// Retains only the elements specified by the predicate, calling `on_remove`
+	/// with the key of every element removed.
+	#[inline]
+	pub fn retain_notify(
+		&mut self,
+		mut f: impl FnMut(Key, &Value) -> bool,
+		on_remove: impl FnMut(Key),
+	) {
+		self.retain_with(|key, value| f(key, value), on_remove);
+	}
+
+	/// Retains only the elements whose key is in `keys`. Stale and duplicate keys
+	/// are ignored.
+	pub fn retain_keys(&mut self, keys: &[Key]) {
+		let mut kept = vec![false; self.elements.len()];
+
+		for &key in keys {
+			if self.contains_key(key) {
+				kept[key.index().try_into_unchecked()] = true;
+			}
+		}
+
+		self.retain_with(|key, _| kept[key.index().try_into_unchecked()], |_| {});
+	}
+
+	/// Retains only the elements for which the predicate returns `Ok`. Elements
+	/// for which it returns `Err` are removed, and `on_remove` is called with
+	/// their key and the error, which can say why they were removed.
+	pub fn retain_categorized<C>(
+		&mut self,
+		mut f: impl FnMut(Key, &Value) -> Result<(), C>,
+		mut on_remove: impl FnMut(Key, C),
+	) {
+		let category = Cell::new(None);
+
+		self.retain_with(
+			|key, value| {
+				f(key, value)
+					.map_err(|reason| category.set(Some(reason)))
+					.is_ok()
+			},
+			|key| on_remove(key, category.take().unwrap_or_else(|| unreachable!())),
+		);
+	}
+
+	/// Removes every element equal to the element kept before it in ascending index
+	/// order, like [`Vec::dedup`], returning the keys of the removed elements.
+	pub fn dedup_by_value(&mut self) -> Vec<Key>
+	where
+		Value: PartialEq,
+	{
+		let mut removed = Vec::new();
+		let mut kept: Option<usize> = None;
+
+		for index in 0..self.elements.len() {
+			let Element::Occupied { version, ref value } = self.elements[index] else {
+				continue;
+			};
+
+			let duplicate = kept.is_some_and(|kept| self.elements[kept].as_ref() == Some(value));
+			let key = Key::new(
+				Key::Index::try_from_checked(index).unwrap_or_else(|| unreachable!()),
+				version,
+			);
+
+			if duplicate && self.try_remove(key).is_some() {
+				removed.push(key);
+			} else {
+				kept = Some(index);
+			}
+		}
+
+		removed
+	}
+
+	/// Retains only the elements specified by the predicate, moving the survivors
+	/// down into the slots freed before them in the same pass. Returns the old and
+	/// new key of every element that was moved; keys of the others stay valid.
+	///
+	/// Tombstones and slots whose version can not be bumped any further are left
+	/// in place. Quarantined slots may be filled, so the quarantine is released.
+	pub fn retain_compacting(&mut self, mut f: impl FnMut(Key, &Value) -> bool) -> Vec<(Key, Key)> {
+		self.assert_writable();
+
+		let mut remap = Vec::new();
+		let mut target = 0;
+
+		for index in 0..self.elements.len() {
+			let Element::Occupied { version, ref value } = self.elements[index] else {
+				if target == index && !matches!(self.elements[index], Element::Vacant { .. }) {
+					target += 1;
+				}
+
+				continue;
+			};
+
+			let from = Key::Index::try_from_checked(index).unwrap_or_else(|| unreachable!());
+			let key = Key::new(from, version);
+
+			if !f(key, value) {
+				if self.elements[index].reset(self.next).is_some() {
+					self.len = try_transform(self.len, |len| len.checked_sub(1))
+						.unwrap_or_else(|| unreachable!());
+
+					self.epoch = self.epoch.wrapping_add(1);
+
+					continue;
+				}
+			} else if target != index {
+				if let Some(value) = self.elements[index].reset(self.next) {
+					let to = Key::Index::try_from_checked(target).unwrap_or_else(|| unreachable!());
+					let (new_version, _) = self.elements[target].set(value);
+
+					remap.push((key, Key::new(to, new_version)));
+
+					target += 1;
+
+					while !matches!(self.elements[target], Element::Vacant { .. }) {
+						target += 1;
+					}
+
+					continue;
+				}
+			}
+
+			if target == index {
+				target += 1;
+			}
+		}
+
+		self.quarantine.clear();
+		self.rethread();
+		self.shrink_max_occupied();
+		self.epoch = self.epoch.wrapping_add(1);
+
+		remap
+	}
+
+	fn retain_with(
+		&mut self,
+		mut f: impl FnMut(Key, &mut Value) -> bool,
+		mut on_remove: impl FnMut(Key),
+	) {
+		self.assert_writable();
+
+		for index in 0..self.elements.len() {
+			// Only reachable once every occupied element was visited and removed.
+			if self.len.try_into_unchecked() == Key::Index::MIN.try_into_unchecked() {
+				break;
+			}
+
+			let element = &mut self.elements[index];
+
+			if let Element::Occupied { version, value } = element {
+				let index = Key::Index::try_from_checked(index).unwrap_or_else(|| unreachable!());
+				let key = Key::new(index, *version);
+
+				// Slots whose version is exhausted can not be freed, so their values
+				// stay in place as with `try_remove`.
+				if !f(key, value) && element.reset(self.next).is_some() {
+					let len = try_transform(self.len, |len| len.checked_sub(1))
+						.unwrap_or_else(|| unreachable!());
+
+					self.free_slot(index);
+					self.len = len;
+					self.epoch = self.epoch.wrapping_add(1);
+
+					on_remove(key);
+				}
+			}
+		}
+
+		self.shrink_max_occupied();
+	}
+}
+
+impl<Key: Referent, Value: Into<f64> + Copy> Arena<Key, Value> {
+	/// Returns the count, mean, and population variance of the values of the
+	/// [`Arena`], computed in a single pass, or `None` if it is empty.
+	#[must_use]
+	pub fn value_stats(&self) -> Option<ValueStats> {
+		let mut stats = ValueStats {
+			count: 0,
+			mean: 0.0,
+			variance: 0.0,
+		};
+
+		// Welford's algorithm, with `variance` holding the sum of squared
+		// differences from the mean until the end.
+		for &value in self.values() {
+			let value = value.into();
+			let delta = value - stats.mean;
+
+			stats.count += 1;
+			stats.mean += delta / stats.count as f64;
+			stats.variance += delta * (value - stats.mean);
+		}
+
+		(stats.count != 0).then(|| ValueStats {
+			variance: stats.variance / stats.count as f64,
+			..stats
+		})
+	}
+}
+
+/// Statistics over the values of an [`Arena`].
+///
+/// Returned by the [`Arena::value_stats`] method.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ValueStats {
+	/// The number of values.
+	pub count: usize,
+
+	/// The mean of the values.
+	pub mean: f64,
+
+	/// The population variance of the values.
+	pub variance: f64,
+}
+
+impl<Key: Referent, Value> Arena<Key, Pin<Box<Value>>> {
+	/// Returns a pinned mutable reference to the value corresponding to the given key.
+	///
+	/// Growing the [`Arena`] moves its slots, so values that must stay pinned
+	/// are stored behind a [`Pin<Box<Value>>`] which keeps them in place.
+	#[inline]
+	#[must_use]
+	pub fn get_pin_mut(&mut self, key: Key) -> Option<Pin<&mut Value>> {
+		self.get_mut(key).map(Pin::as_mut)
+	}
+}
+
+/// The error returned by [`Arena::try_reserve`] and [`Arena::try_reserve_exact`]
+/// when the backing storage could not grow.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TryReserveError(alloc::collections::TryReserveError);
+
+impl Display for TryReserveError {
+	#[inline]
+	fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+		Display::fmt(&self.0, f)
+	}
+}
+
+impl core::error::Error for TryReserveError {}
+
+/// The error returned by [`Arena::set_capacity`] when the capacity can not be set.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CapacityError {
+	/// Slots past the target are still in use, so at least `required` slots must be kept.
+	Occupied { required: usize },
+
+	/// The target is more slots than a `Key::Index` can address.
+	Unrepresentable,
+}
+
+impl Display for CapacityError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+		match self {
+			Self::Occupied { required } => {
+				write!(f, "slots are in use up to a capacity of {required}")
+			}
+			Self::Unrepresentable => f.write_str("capacity is not representable by the key"),
+		}
+	}
+}
+
+impl core::error::Error for CapacityError {}
+
+/// A slot of an [`Arena`] that is reserved but not yet filled.
+/// Dropping it without calling [`PendingKey::fill`] leaves the slot free.
+///
+/// Created by the [`Arena::reserve_key`] method.
+#[must_use = "the slot is released unless filled"]
+pub struct PendingKey<'a, Key: Referent, Value> {
+	arena: &'a mut Arena<Key, Value>,
+	key: Key,
+}
+
+impl<Key: Referent, Value> PendingKey<'_, Key, Value> {
+	/// Returns the key the value will be stored at.
+	#[inline]
+	#[must_use]
+	pub const fn key(&self) -> Key {
+		self.key
+	}
+
+	/// Fills the slot with the value, returning the key.
+	#[inline]
+	pub fn fill(self, value: Value) -> Key {
+		self.arena.occupy(value);
+
+		self.key
+	}
+}
+
+/// A key bundled with a borrow of the [`Arena`] it belongs to, so the value it
+/// refers to is known to be present for as long as the handle lives. Use
+/// [`Handle::key`] to get a plain key for storing long-term.
+///
+/// Created by the [`Arena::handle`] method.
+pub struct Handle<'a, Key: Referent, Value> {
+	key: Key,
+	value: &'a Value,
+}
+
+impl<'a, Key: Referent, Value> Handle<'a, Key, Value> {
+	/// Returns the key of the handle.
+	#[inline]
+	#[must_use]
+	pub const fn key(&self) -> Key {
+		self.key
+	}
+
+	/// Returns a reference to the value of the handle, with the lifetime of
+	/// the [`Arena`].
+	#[inline]
+	#[must_use]
+	pub const fn get(&self) -> &'a Value {
+		self.value
+	}
+}
+
+impl<Key: Referent, Value> Clone for Handle<'_, Key, Value> {
+	#[inline]
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<Key: Referent, Value> Copy for Handle<'_, Key, Value> {}
+
+impl<Key: Referent, Value> Deref for Handle<'_, Key, Value> {
+	type Target = Value;
+
+	#[inline]
+	fn deref(&self) -> &Self::Target {
+		self.value
+	}
+}
+
+/// A raw slot position, such as one returned by [`Arena::stable_index`], used to
+/// index an [`Arena`] directly.
+///
+/// Indexing by a [`RawSlot`] does not check any version, so it reaches whatever
+/// value occupies the slot now. It is meant for code that has already checked
+/// the slot still holds the value it expects.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct RawSlot(pub usize);
+
+/// A non-panicking counterpart to [`Index`], for generic code that needs
+/// to handle missing elements.
+pub trait TryIndex<Idx> {
+	/// The type returned by the indexing operation.
+	type Output: ?Sized;
+
+	/// Returns a reference to the element at the given index, if present.
+	fn try_index(&self, index: Idx) -> Option<&Self::Output>;
+}
+
+impl<Key: Referent, Value> TryIndex<Key> for Arena<Key, Value> {
+	type Output = Value;
+
+	#[inline]
+	fn try_index(&self, key: Key) -> Option<&Self::Output> {
+		self.get(key)
+	}
+}
+
+impl<Key: Referent, Value> Index<Key> for Arena<Key, Value> {
+	type Output = Value;
+
+	#[inline]
+	fn index(&self, key: Key) -> &Self::Output {
+		self.get(key).expect("should be able to get")
+	}
+}
+
+impl<Key: Referent, Value> IndexMut<Key> for Arena<Key, Value> {
+	#[inline]
+	fn index_mut(&mut self, key: Key) -> &mut Self::Output {
+		self.get_mut(key).expect("should be able to get_mut")
+	}
+}
+
+impl<Key: Referent, Value> Index<&Key> for Arena<Key, Value> {
+	type Output = Value;
+
+	#[inline]
+	fn index(&self, key: &Key) -> &Self::Output {
+		&self[*key]
+	}
+}
+
+impl<Key: Referent, Value> IndexMut<&Key> for Arena<Key, Value> {
+	#[inline]
+	fn index_mut(&mut self, key: &Key) -> &mut Self::Output {
+		&mut self[*key]
+	}
+}
+
+impl<Key: Referent, Value> Index<RawSlot> for Arena<Key, Value> {
+	type Output = Value;
+
+	/// Returns the value occupying the slot, whatever its version.
+	///
+	/// # Panics
+	///
+	/// Panics if the slot is vacant or out of range.
+	#[inline]
+	fn index(&self, slot: RawSlot) -> &Self::Output {
+		self.elements
+			.get(slot.0)
+			.and_then(Element::as_ref)
+			.expect("slot should be occupied")
+	}
+}
+
+impl<Key: Referent, Value> FromIterator<Value> for Arena<Key, Value> {
+	/// Creates an [`Arena`] holding the values in order, at ascending indices.
+	///
+	/// # Panics
+	///
+	/// Panics if a `Key::Index` can not represent the number of values.
+	fn from_iter<T: IntoIterator<Item = Value>>(iter: T) -> Self {
+		let iter = iter.into_iter();
+		let mut arena = Self::with_capacity(iter.size_hint().0);
+
+		for value in iter {
+			let _key = arena.insert(value);
+		}
+
+		arena
+	}
+}
+
+impl<Key: Referent, Value> Extend<Value> for Arena<Key, Value> {
+	/// Inserts the values in order, reusing free slots before growing.
+	///
+	/// # Panics
+	///
+	/// Panics if the [`Arena`] reaches capacity.
+	fn extend<T: IntoIterator<Item = Value>>(&mut self, iter: T) {
+		let iter = iter.into_iter();
+
+		self.reserve(iter.size_hint().0);
+
+		for value in iter {
+			let _key = self.insert(value);
+		}
+	}
+}
+
+impl<Key: Referent, Value> Extend<Value> for &mut Arena<Key, Value> {
+	/// See [`Arena::extend`].
+	#[inline]
+	fn extend<T: IntoIterator<Item = Value>>(&mut self, iter: T) {
+		(**self).extend(iter);
+	}
+}
+
+/// Two arenas are equal if they hold equal values under equal keys, regardless of
+/// their capacity or free list.
+impl<Key: Referent + PartialEq, Value: PartialEq> PartialEq for Arena<Key, Value> {
+	fn eq(&self, other: &Self) -> bool {
+		self.len() == other.len() && self.iter().eq(other.iter())
+	}
+}
+
+impl<Key: Referent + Eq, Value: Eq> Eq for Arena<Key, Value> {}
+
+/// Hashes the keys and values in ascending index order, consistent with
+/// the [`PartialEq`] implementation.
+impl<Key: Referent + Hash, Value: Hash> Hash for Arena<Key, Value> {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		state.write_usize(self.len());
+
+		for entry in self {
+			entry.hash(state);
+		}
+	}
+}
+
+impl<Key: Referent + Debug, Value: Debug> Debug for Arena<Key, Value> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_map().entries(self.iter()).finish()
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use core::{
+		marker::PhantomPinned,
+		num::{NonZeroU32, NonZeroU64, NonZeroU8},
+		pin::Pin,
+	};
+	use std::{
+		collections::HashMap,
+		hash::{DefaultHasher, Hash, Hasher},
+		sync::{Arc, Mutex},
+	};
+
+	use crate::{
+		collection::{Arena, CapacityError, RawSlot, TryIndex},
+		element::Element,
+		referent::{BoundedId, Id, Nil, PackedId, Referent, Similar},
+	};
+
+	#[test]
+	fn add_and_remove() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		let a = arena.insert(10);
+		let b = arena.insert(20);
+		let c = arena.insert(30);
+
+		assert_eq!(arena[a], 10);
+		assert_eq!(arena[b], 20);
+		assert_eq!(arena[c], 30);
+
+		assert_eq!(arena.len(), 3);
+
+		arena.remove(a);
+
+		assert_eq!(arena.len(), 2);
+
+		arena.remove(b);
+
+		assert_eq!(arena.len(), 1);
+
+		arena.remove(c);
+
+		assert_eq!(arena.len(), 0);
+	}
+
+	#[test]
+	fn remove_twice() {
+		let mut arena = Arena::<Id, usize>::new();
+
+		let a = arena.insert(10);
+
+		assert_eq!(arena.try_remove(a), Some(10));
+		assert_eq!(arena.try_remove(a), None);
+	}
+
+	#[test]
+	fn remove_twice_nil() {
+		let mut arena = Arena::<Id<u32, Nil>, usize>::new();
+
+		let a = arena.insert(10);
+
+		assert_eq!(arena.try_remove(a), Some(10));
+		assert_eq!(arena.try_remove(a), None);
+	}
+
+	#[test]
+	fn add_and_clear() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		let a = arena.insert(10);
+		let b = arena.insert(20);
+		let c = arena.insert(30);
+
+		assert_eq!(arena[a], 10);
+		assert_eq!(arena[b], 20);
+		assert_eq!(arena[c], 30);
+
+		assert_eq!(arena.len(), 3);
+
+		arena.clear();
+
+		assert_eq!(arena.len(), 0);
+
+		assert_eq!(arena.get(a), None);
+		assert_eq!(arena.get(b), None);
+		assert_eq!(arena.get(c), None);
+	}
+
+	#[test]
+	fn clear_invalidates_keys() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		let keys: Vec<_> = (0..16).map(|i| arena.insert(i)).collect();
+
+		arena.remove(keys[3]);
+		arena.clear();
+
+		let _new: Vec<_> = (0..16).map(|i| arena.insert(i)).collect();
+
+		assert!(keys.iter().all(|&key| arena.get(key).is_none()));
+	}
+
+	#[test]
+	fn clear_aliases_nil_keys() {
+		let mut arena = Arena::<Id<u32, Nil>, u32>::new();
+
+		let a = arena.insert(10);
+
+		arena.clear();
+
+		let b = arena.insert(20);
+
+		assert_eq!(a, b);
+		assert_eq!(arena.get(a), Some(&20));
+	}
+
+	#[test]
+	fn clear_near_saturation() {
+		let mut arena = Arena::<Id<u32, NonZeroU8>, u32>::new();
+
+		for _ in 0..253 {
+			let key = arena.insert(0);
+
+			arena.remove(key);
+		}
+
+		let a = arena.insert(10);
+
+		assert_eq!(a.version(), NonZeroU8::new(254).unwrap());
+
+		arena.clear();
+
+		let b = arena.insert(20);
+
+		assert_eq!(b.version(), NonZeroU8::MAX);
+		assert_eq!(arena.get(a), None);
+		assert_eq!(arena.get(b), Some(&20));
+
+		let c = arena.insert(30);
+
+		arena.clear();
+
+		assert_eq!(arena.len(), 1);
+		assert_eq!(arena.get(b), Some(&20));
+		assert_eq!(arena.get(c), None);
+	}
+
+	#[test]
+	fn top_n_by() {
+		let mut arena = Arena::<Id, (&str, u32)>::new();
+
+		let keys: Vec<_> = [("a", 5), ("b", 9), ("c", 5), ("d", 1), ("e", 9), ("f", 7)]
+			.into_iter()
+			.map(|entry| arena.insert(entry))
+			.collect();
+
+		arena.remove(keys[4]);
+
+		let top: Vec<_> = arena
+			.top_n_by(3, |entry| entry.1)
+			.into_iter()
+			.map(|(key, entry)| (key, entry.0))
+			.collect();
+
+		assert_eq!(top, [(keys[1], "b"), (keys[5], "f"), (keys[0], "a")]);
+		assert!(arena.top_n_by(0, |entry| entry.1).is_empty());
+
+		let all: Vec<_> = arena
+			.top_n_by(10, |entry| entry.1)
+			.into_iter()
+			.map(|(_, entry)| entry.0)
+			.collect();
+
+		assert_eq!(all, ["b", "f", "a", "c", "d"]);
+	}
+
+	#[test]
+	fn split_off() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		let keys: Vec<_> = (0..8).map(|i| arena.insert(i * 10)).collect();
+
+		arena.remove(keys[2]);
+
+		let (odd, remap) = arena.split_off(|_, value| (value / 10) % 2 == 1);
+
+		assert_eq!(remap.len(), 4);
+		assert!(arena.values().eq(&[0, 40, 60]));
+		assert!(odd.values().eq(&[10, 30, 50, 70]));
+
+		for (old, new) in remap {
+			assert_eq!(arena.get(old), None);
+			assert_eq!(odd[new], old.index() * 10);
+		}
+
+		for key in [keys[0], keys[4], keys[6]] {
+			assert_eq!(arena[key], key.index() * 10);
+		}
+
+		let indices: Vec<_> = (0..5).map(|i| arena.insert(i).index()).collect();
+
+		assert_eq!(indices, [7, 5, 3, 1, 2]);
+	}
+
+	#[test]
+	fn handle() {
+		struct Node {
+			name: &'static str,
+			edges: Vec<Id>,
+		}
+
+		let mut arena = Arena::<Id, Node>::new();
+
+		let leaf = arena.insert(Node {
+			name: "leaf",
+			edges: Vec::new(),
+		});
+		let root = arena.insert(Node {
+			name: "root",
+			edges: vec![leaf],
+		});
+
+		let handle = arena.handle(root).unwrap();
+		let edges: Vec<_> = handle
+			.edges
+			.iter()
+			.map(|&edge| arena.handle(edge).unwrap())
+			.collect();
+
+		assert_eq!(handle.name, "root");
+		assert_eq!(edges[0].get().name, "leaf");
+
+		let stored = edges[0].key();
+
+		arena.remove(stored);
+
+		assert_eq!(stored, leaf);
+		assert!(arena.handle(stored).is_none());
+	}
+
+	#[test]
+	fn index_by_ref() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		let keys = [arena.insert(10), arena.insert(20)];
+
+		for key in &keys {
+			arena[key] += 1;
+		}
+
+		assert_eq!(&arena[&keys[0]], &11);
+		assert_eq!(arena[&keys[1]], arena[keys[1]]);
+	}
+
+	#[test]
+	fn index_raw_slot() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		let a = arena.insert(10);
+		let b = arena.insert(20);
+		let slot = RawSlot(arena.stable_index(b).unwrap());
+
+		assert_eq!(arena[slot], 20);
+
+		arena.remove(b);
+
+		let c = arena.insert(30);
+
+		assert_eq!(c.index(), b.index());
+		assert_eq!(arena[slot], 30);
+		assert_eq!(arena[RawSlot(arena.stable_index(a).unwrap())], 10);
+	}
+
+	#[test]
+	#[should_panic(expected = "slot should be occupied")]
+	fn index_raw_slot_vacant() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		let a = arena.insert(10);
+		let slot = RawSlot(arena.stable_index(a).unwrap());
+
+		arena.remove(a);
+
+		let _ = arena[slot];
+	}
+
+	#[test]
+	fn insert_within_capacity() {
+		let mut arena = Arena::<Id, String>::with_capacity(2);
+		let capacity = arena.capacity();
+
+		let keys: Vec<_> = (0..capacity)
+			.map(|i| arena.insert_within_capacity(i.to_string()).unwrap())
+			.collect();
+
+		assert_eq!(
+			arena.insert_within_capacity("spill".to_string()),
+			Err("spill".to_string())
+		);
+		assert_eq!(arena.capacity(), capacity);
+
+		arena.remove(keys[0]);
+
+		let key = arena.insert_within_capacity("fits".to_string()).unwrap();
+
+		assert_eq!(key.index(), keys[0].index());
+		assert_eq!(arena[key], "fits");
+
+		let mut arena = Arena::<Id<u8>, u8>::new();
+
+		for i in 0..u8::MAX {
+			let _ = arena.insert(i);
+		}
+
+		assert!(arena.is_full());
+		assert_eq!(arena.insert_within_capacity(7), Err(7));
+	}
+
+	#[test]
+	fn retain_keys() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		let keys: Vec<_> = (0..6).map(|i| arena.insert(i)).collect();
+
+		arena.remove(keys[1]);
+
+		let stale = keys[1];
+		let reused = arena.insert(10);
+
+		arena.retain_keys(&[keys[4], stale, keys[0], keys[4]]);
+
+		assert!(arena.iter().eq([(keys[0], &0), (keys[4], &4)]));
+		assert_eq!(arena.get(reused), None);
+
+		let mut indices: Vec<_> = (0..4).map(|i| arena.insert(i).index()).collect();
+
+		indices.sort_unstable();
+
+		assert_eq!(indices, [1, 2, 3, 5]);
+	}
+
+	#[test]
+	fn try_drain_exhausted() {
+		let mut arena = Arena::<Id<u32, NonZeroU8>, u32>::new();
+
+		for _ in 0..254 {
+			let key = arena.insert(0);
+
+			arena.remove(key);
+		}
+
+		let a = arena.insert(10);
+		let b = arena.insert(20);
+
+		assert_eq!(a.version(), NonZeroU8::MAX);
+
+		let drained = arena.try_drain(|key, value| Ok::<_, ()>((key, value)));
+
+		assert_eq!(drained, Ok(vec![(b, 20)]));
+		assert_eq!(arena.len(), 1);
+		assert_eq!(arena.get(a), Some(&10));
+	}
+
+	#[test]
+	fn retain_exhausted() {
+		let mut arena = Arena::<Id<u32, NonZeroU8>, u32>::new();
+
+		for _ in 0..254 {
+			let key = arena.insert(0);
+
+			arena.remove(key);
+		}
+
+		let a = arena.insert(10);
+		let b = arena.insert(20);
+
+		assert_eq!(a.version(), NonZeroU8::MAX);
+		assert_eq!(arena.try_remove(a), None);
+
+		let mut removed = Vec::new();
+
+		arena.retain_notify(|_, _| false, |key| removed.push(key));
+
+		assert_eq!(removed, [b]);
+		assert_eq!(arena.len(), 1);
+		assert_eq!(arena.get(a), Some(&10));
+		assert_eq!(arena.get(b), None);
+	}
+
+	#[test]
+	fn packed_id() {
+		type Index = <PackedId<24> as Referent>::Index;
+		type Version = <PackedId<24> as Referent>::Version;
+
+		let max = (1 << 24) - 1;
+
+		assert!(Index::try_from_checked(max).is_some());
+		assert!(Index::try_from_checked(max + 1).is_none());
+		assert!(Version::try_from_checked(NonZeroU64::new(255).unwrap()).is_some());
+		assert!(Version::try_from_checked(NonZeroU64::new(256).unwrap()).is_none());
+
+		let key = PackedId::<24>::new(Index::MAX, Version::MAX);
+
+		assert_eq!(key.index().try_into_unchecked(), max);
+		assert_eq!(key.version(), Version::MAX);
+
+		let mut arena = Arena::<PackedId<24>, u32>::new();
+
+		for _ in 0..254 {
+			let key = arena.insert(0);
+
+			arena.remove(key);
+		}
+
+		let a = arena.insert(10);
+
+		assert_eq!(a.version(), Version::MAX);
+		assert_eq!(arena.try_remove(a), None);
+		assert_eq!(arena[a], 10);
+
+		let b = arena.insert(20);
+
+		assert_eq!(b.index().try_into_unchecked(), 1);
+	}
+
+	#[test]
+	fn packed_id_capacity() {
+		let mut arena = Arena::<PackedId<12>, usize>::new();
+
+		for i in 0..4095 {
+			assert_eq!(arena.insert(i).index().try_into_unchecked(), i);
+		}
+
+		assert_eq!(arena.try_insert(4095), None);
+		assert_eq!(arena.len(), 4095);
+	}
+
+	#[test]
+	fn deterministic_keys() {
+		let mut first = Arena::<Id, u32>::new();
+		let mut second = Arena::<Id, u32>::with_capacity(37);
+		let mut live = Vec::new();
+		let mut seed = 0x2545_F491_u32;
+
+		for value in 0..1000 {
+			seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+
+			if seed.is_multiple_of(3) && !live.is_empty() {
+				let key = live.swap_remove(seed as usize % live.len());
+
+				assert_eq!(first.remove(key), second.remove(key));
+			} else {
+				let key = first.insert_deterministic(value);
+
+				assert_eq!(key, second.insert_deterministic(value));
+
+				live.push(key);
+			}
+
+			if value == 500 {
+				second.reserve(1000);
+			}
+		}
+	}
+
+	#[test]
+	fn for_each_mut_sorted() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		let keys: Vec<_> = (0..6).map(|i| arena.insert(i)).collect();
+
+		arena.remove(keys[2]);
+
+		let mut visited = Vec::new();
+
+		arena.for_each_mut_sorted(
+			&[keys[4], keys[0], keys[2], keys[5], keys[0]],
+			|key, value| {
+				visited.push(key);
+
+				*value += 10;
+			},
+		);
+
+		assert_eq!(visited, [keys[0], keys[4], keys[5]]);
+		assert!(arena.values().eq(&[10, 1, 3, 14, 15]));
+	}
+
+	#[test]
+	fn zero_sized_values() {
+		let mut arena = Arena::<Id, ()>::new();
+
+		arena.reserve(10);
+
+		assert!(arena.capacity() >= 10);
+		assert!(arena.memory_usage() >= 10 * core::mem::size_of::<Id>());
+
+		let keys: Vec<_> = (0..10).map(|_| arena.insert(())).collect();
+
+		assert_eq!(arena.len(), 10);
+		assert!(keys.iter().all(|&key| arena.get(key).is_some()));
+	}
+
+	#[test]
+	fn retain_notify() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		let keys: Vec<_> = (0..10).map(|i| arena.insert(i)).collect();
+		let mut removed = Vec::new();
+
+		arena.retain_notify(|_, &value| value % 3 != 0, |key| removed.push(key));
+
+		assert_eq!(removed, [keys[0], keys[3], keys[6], keys[9]]);
+		assert!(removed.iter().all(|&key| arena.get(key).is_none()));
+		assert_eq!(arena.len(), 6);
+	}
+
+	#[test]
+	fn try_drain() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		let keys: Vec<_> = (0..6).map(|i| arena.insert(i)).collect();
+
+		arena.remove(keys[1]);
+
+		let result = arena.try_drain(|_, value| {
+			if value < 3 {
+				Ok(value * 10)
+			} else {
+				Err(value)
+			}
+		});
+
+		assert_eq!(result, Err(3));
+		assert_eq!(arena.len(), 2);
+		assert!(keys[..4].iter().all(|&key| arena.get(key).is_none()));
+		assert_eq!(arena[keys[4]], 4);
+		assert_eq!(arena[keys[5]], 5);
+
+		let result = arena.try_drain(|key, value| Ok::<_, ()>((key, value)));
+
+		assert_eq!(result, Ok(vec![(keys[4], 4), (keys[5], 5)]));
+		assert!(arena.is_empty());
+	}
+
+	#[test]
+	fn retain_mut() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		let keys: Vec<_> = (0..6).map(|i| arena.insert(i * 10)).collect();
+
+		arena.retain_mut(|_, value| {
+			*value = value.saturating_sub(25);
+
+			*value != 0
+		});
+
+		assert_eq!(arena.len(), 3);
+		assert!(keys[..3].iter().all(|&key| arena.get(key).is_none()));
+		assert!(arena.values().copied().eq([5, 15, 25]));
+
+		let indices: Vec<_> = (0..3).map(|i| arena.insert(i).index()).collect();
+
+		assert_eq!(indices, [2, 1, 0]);
+	}
+
+	#[test]
+	fn retain_compacting() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		let keys: Vec<_> = (0..10).map(|i| arena.insert(i)).collect();
+
+		arena.remove(keys[1]);
+
+		let remap = arena.retain_compacting(|_, &value| value % 3 != 0);
+
+		assert_eq!(arena.len(), 5);
+		assert!(arena.keys().all(|key| key.index() < 5));
+		assert!(arena.values().copied().eq([2, 4, 5, 7, 8]));
+		assert!(remap.iter().all(|&(old, new)| arena[new] == old.index()));
+		assert!(remap.iter().all(|&(old, _)| arena.get(old).is_none()));
+		assert!(keys
+			.iter()
+			.all(|&key| arena.get(key).is_none_or(|&value| value == key.index())));
+
+		let indices: Vec<_> = (0..3).map(|i| arena.insert(i).index()).collect();
+
+		assert_eq!(indices, [5, 6, 7]);
+	}
+
+	#[test]
+	fn dedup_by_value() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		let keys: Vec<_> = [1, 1, 2, 2, 2, 1, 3, 3]
+			.into_iter()
+			.map(|value| arena.insert(value))
+			.collect();
+
+		arena.remove(keys[3]);
+
+		let removed = arena.dedup_by_value();
+
+		assert_eq!(removed, [keys[1], keys[4], keys[7]]);
+		assert!(arena.values().copied().eq([1, 2, 1, 3]));
+		assert!(removed.iter().all(|&key| arena.get(key).is_none()));
+		assert!(arena.dedup_by_value().is_empty());
+	}
+
+	#[test]
+	fn from_iter() {
+		let arena: Arena<Id, u32> = (0..100).map(|i| i * 2).collect();
+
+		assert_eq!(arena.len(), 100);
+		assert_eq!(arena.capacity(), 100);
+		assert!(arena
+			.iter()
+			.enumerate()
+			.all(|(i, (key, &value))| { key.index() as usize == i && value == key.index() * 2 }));
+	}
+
+	#[test]
+	fn extend() {
+		fn fill(mut sink: impl Extend<u32>) {
+			sink.extend([40, 50, 60, 70]);
+		}
+
+		let mut arena: Arena<Id, u32> = (0..4).collect();
+
+		arena.remove(Id::new(1, NonZeroU32::MIN));
+		arena.remove(Id::new(2, NonZeroU32::MIN));
+
+		fill(&mut arena);
+
+		assert_eq!(arena.len(), 6);
+		assert!(arena.values().copied().eq([0, 50, 40, 3, 60, 70]));
+
+		arena.extend(core::iter::empty());
+
+		assert_eq!(arena.len(), 6);
+	}
+
+	#[test]
+	fn with_free_order() {
+		let mut arena = Arena::<Id, u32>::with_free_order(vec![3, 1, 5, 1]);
+
+		let indices: Vec<_> = (0..8).map(|i| arena.insert(i).index()).collect();
+
+		assert_eq!(indices, [3, 1, 5, 0, 2, 4, 6, 7]);
+		assert!(arena.values().eq(&[3, 1, 4, 0, 5, 2, 6, 7]));
+	}
+
+	#[test]
+	fn try_index() {
+		fn lookup<T: TryIndex<Id, Output = u32>>(store: &T, key: Id) -> u32 {
+			store.try_index(key).copied().unwrap_or_default()
+		}
+
+		let mut arena = Arena::<Id, u32>::new();
+
+		let a = arena.insert(10);
+		let b = arena.insert(20);
+
+		arena.remove(b);
+
+		assert_eq!(lookup(&arena, a), 10);
+		assert_eq!(lookup(&arena, b), 0);
+	}
+
+	#[test]
+	fn first_growth() {
+		let mut arena = Arena::<Id, [u8; 2048]>::new();
+		let mut capacity = arena.capacity();
+		let mut grows = 0;
+
+		for _ in 0..4 {
+			let _id = arena.insert([0; 2048]);
+
+			if arena.capacity() != capacity {
+				capacity = arena.capacity();
+				grows += 1;
+			}
+		}
+
+		assert_eq!(grows, 1);
+	}
+
+	#[test]
+	fn with_capacity_no_realloc() {
+		fn check<Key: Referent>(capacity: usize) {
+			let mut arena = Arena::<Key, usize>::with_capacity(capacity);
+			let elements = arena.elements.as_ptr();
+
+			for i in 0..capacity {
+				let _key = arena.insert(i);
+
+				assert_eq!(arena.elements.as_ptr(), elements);
+			}
+		}
+
+		for capacity in [1, 3, 4, 5, 16, 100, 255] {
+			check::<Id<u8>>(capacity);
+			check::<Id<u16>>(capacity);
+			check::<Id>(capacity);
+			check::<Id<usize>>(capacity);
+		}
+	}
+
+	#[test]
+	fn capacity_is_slot_count() {
+		fn check<Key: Referent>(arena: &Arena<Key, usize>) {
+			let spare = arena
+				.elements
+				.capacity()
+				.min(Key::Index::MAX.try_into_unchecked());
+
+			assert_eq!(arena.capacity(), spare);
+		}
+
+		for capacity in [0, 1, 3, 4, 5, 100] {
+			let mut arena = Arena::<Id, usize>::with_capacity(capacity);
+			let mut last = arena.capacity();
+
+			assert!(last >= capacity);
+
+			for i in 0..50 {
+				let _key = arena.insert(i);
+
+				assert!(arena.capacity() >= last);
+				check(&arena);
+
+				last = arena.capacity();
+			}
+		}
+
+		// Filtering hides the exact length, so `collect` grows the storage as it
+		// goes and may leave spare room that has to become vacant slots.
+		let slots = (0..20).map(|i| (i % 3 == 0).then_some(i)).filter(|_| true);
+		let mut arena = Arena::<Id<u8>, usize>::from_slots(slots);
+
+		check(&arena);
+
+		arena.reserve(1000);
+		check(&arena);
+
+		arena.shrink_to(30);
+		check(&arena);
+
+		for key in arena.keys().step_by(2).collect::<Vec<_>>() {
+			arena.remove(key);
+		}
+
+		let _remap = arena.shrink_and_compact();
+
+		check(&arena);
+	}
+
+	#[test]
+	#[should_panic(expected = "free list corrupted: next points at occupied slot 1")]
+	fn corrupted_free_list() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		let _a = arena.insert(10);
+		let _b = arena.insert(20);
+
+		arena.next = 1;
+
+		let _c = arena.insert(30);
+	}
+
+	#[test]
+	fn fill_vacant_matches_push_loop() {
+		fn check<Key: Referent>(capacity: usize) {
+			let mut expected = Vec::with_capacity(capacity);
+
+			for index in 0..expected.capacity() {
+				if let Some(next) = Key::Index::try_from_checked(index + 1) {
+					expected.push(Element::<_, _, ()>::Vacant {
+						version: Key::Version::MIN,
+						next,
+					});
+				} else {
+					break;
+				}
+			}
+
+			let mut arena = Arena::<Key, ()>::new();
+
+			arena.elements.reserve_exact(capacity);
+			arena.fill_vacant();
+
+			assert_eq!(arena.elements.len(), expected.len());
+			assert!(arena.elements.iter().zip(&expected).all(|pair| match pair {
+				(
+					Element::Vacant { version, next },
+					Element::Vacant {
+						version: other_version,
+						next: other_next,
+					},
+				) => {
+					version.try_into_unchecked() == other_version.try_into_unchecked()
+						&& next.try_into_unchecked() == other_next.try_into_unchecked()
+				}
+				_ => false,
+			}));
+		}
+
+		for capacity in [0, 1, 4, 254, 255, 256, 1000, 70_000] {
+			check::<Id<u8>>(capacity);
+			check::<Id<u16>>(capacity);
+			check::<Id>(capacity);
+		}
+	}
+
+	#[test]
+	fn max_and_min_by_key() {
+		let mut arena = Arena::<Id, i32>::new();
+
+		assert_eq!(arena.max_by_key(|&value| value), None);
+		assert_eq!(arena.min_by_key(|&value| value), None);
+
+		let _a = arena.insert(5);
+		let b = arena.insert(-7);
+		let c = arena.insert(7);
+		let _d = arena.insert(-7);
+
+		assert_eq!(arena.max_by_key(|value| value.abs()), Some((b, &-7)));
+		assert_eq!(arena.min_by_key(|&value| value), Some((b, &-7)));
+		assert_eq!(arena.max_by_key(|&value| value), Some((c, &7)));
+	}
+
+	#[test]
+	fn retain_visits_once() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		let keys: Vec<_> = (0..64).map(|i| arena.insert(i)).collect();
+
+		for &key in keys.iter().step_by(5) {
+			arena.remove(key);
+		}
+
+		for keep in [|value: u32| value.is_multiple_of(2), |_| false] {
+			let expected: Vec<_> = arena.keys().collect();
+			let mut visited = Vec::new();
+
+			arena.retain(|key, &value| {
+				visited.push(key);
+
+				keep(value)
+			});
+
+			assert_eq!(visited, expected);
+		}
+
+		assert!(arena.is_empty());
+	}
+
+	#[test]
+	fn reserve_key() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		let a = arena.insert(10);
+		let pending = arena.reserve_key().unwrap();
+		let b = pending.key();
+
+		assert_eq!(pending.fill(20), b);
+		assert_eq!(arena[b], 20);
+
+		let pending = arena.reserve_key().unwrap();
+		let c = pending.key();
+
+		drop(pending);
+
+		assert_eq!(arena.len(), 2);
+		assert_eq!(arena.get(c), None);
+		assert_eq!(arena.insert(30), c);
+		assert_eq!(arena[a], 10);
+	}
+
+	#[test]
+	fn tombstones() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		let a = arena.insert(10);
+		let b = arena.insert(20);
+
+		assert_eq!(arena.remove_tombstoned(a), Some(&10));
+		assert_eq!(arena.remove_tombstoned(a), None);
+		assert_eq!(arena.get(a), None);
+		assert_eq!(arena.get_tombstone(a), Some(&10));
+		assert_eq!(arena.get_tombstone(b), None);
+		assert_eq!(arena.len(), 1);
+
+		let c = arena.insert(30);
+
+		assert_ne!(a.index(), c.index());
+
+		arena.purge_tombstones();
+
+		assert_eq!(arena.get_tombstone(a), None);
+		assert_eq!(arena.insert(40).index(), a.index());
+		assert_eq!(arena.get(a), None);
+	}
+
+	#[test]
+	fn shrink_and_compact() {
+		let mut arena = Arena::<Id, u32>::with_capacity(16);
+
+		let keys: Vec<_> = (0..8).map(|i| arena.insert(i)).collect();
+
+		for &key in &keys[..6] {
+			if key.index() % 2 == 0 {
+				arena.remove(key);
+			}
+		}
+
+		let remap = arena.shrink_and_compact();
+
+		assert_eq!(arena.len(), 5);
+		assert_eq!(arena.capacity(), arena.len());
+		assert!(remap.iter().all(|&(old, new)| arena[new] == old.index()));
+		assert!(remap.iter().all(|&(old, _)| arena.get(old).is_none()));
+
+		for (i, &key) in keys.iter().enumerate() {
+			if i % 2 == 1 && !remap.iter().any(|&(old, _)| old == key) {
+				assert_eq!(arena[key], key.index());
+			}
+		}
+
+		assert!(arena.values().copied().eq([1, 3, 5, 6, 7]));
+
+		let key = arena.insert(8);
+
+		assert_eq!(key.index(), 5);
+	}
+
+	#[test]
+	fn shrink_and_compact_keeps_stale_keys_invalid() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		let a = arena.insert(0);
+		let b = arena.insert(1);
+
+		arena.remove(a);
+
+		let remap = arena.shrink_and_compact();
+
+		assert_eq!(remap.len(), 1);
+		assert_eq!(remap[0].0, b);
+
+		let c = arena.insert(2);
+
+		assert_eq!(c.index(), b.index());
+		assert_ne!(c, b);
+		assert_eq!(arena.get(b), None);
+		assert_eq!(arena[remap[0].1], 1);
+	}
+
+	#[test]
+	fn canonicalize() {
+		let mut first = Arena::<Id, u32>::new();
+		let mut second = Arena::<Id, u32>::with_capacity(16);
+
+		let a = first.insert(0);
+		let _b = first.insert(10);
+		let _c = first.insert(20);
+
+		first.remove(a);
+
+		let _d = second.insert(20);
+		let e = second.insert(0);
+		let _f = second.insert(10);
+
+		second.remove(e);
+
+		let first = first.canonicalize();
+		let second = second.canonicalize();
+
+		assert_eq!(first.capacity(), second.capacity());
+		assert_eq!(first, second);
+		assert!(first.values().eq(&[10, 20]));
+		assert!(first.keys().all(|key| key.version() == NonZeroU32::MIN));
+
+		let first: Arena<Id, f64> = [0.5, f64::NAN, -1.0].into_iter().collect();
+		let second: Arena<Id, f64> = [-1.0, 0.5, f64::NAN].into_iter().collect();
+
+		let first = first.canonicalize_by(f64::total_cmp);
+		let second = second.canonicalize_by(f64::total_cmp);
+
+		let bits = |arena: &Arena<Id, f64>| -> Vec<u64> {
+			arena.values().map(|value| value.to_bits()).collect()
+		};
+
+		assert_eq!(bits(&first), bits(&second));
+		assert_eq!(bits(&first)[0], (-1.0f64).to_bits());
+	}
+
+	#[test]
+	fn for_each_mut() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		let keys: Vec<_> = (0..6).map(|i| arena.insert(i)).collect();
+
+		arena.remove(keys[1]);
+		arena.remove(keys[4]);
+
+		let mut visited = Vec::new();
+
+		arena.for_each_mut(|key, value| {
+			visited.push(key);
+
+			*value *= 2;
+		});
+
+		assert_eq!(visited, [keys[0], keys[2], keys[3], keys[5]]);
+		assert!(arena.values().eq(&[0, 4, 6, 10]));
+	}
+
+	#[test]
+	fn from_slots() {
+		let slots = [Some(10), None, Some(20), None, None, Some(30)];
+		let mut arena = Arena::<Id, u32>::from_slots(slots);
+
+		assert_eq!(arena.len(), 3);
+
+		for (index, slot) in slots.iter().enumerate() {
+			let key = Id::new(index.try_into().unwrap(), NonZeroU32::MIN);
+
+			assert_eq!(arena.get(key), slot.as_ref());
+		}
+
+		let indices: Vec<_> = (0..4).map(|i| arena.insert(i).index()).collect();
+
+		assert_eq!(indices, [1, 3, 4, 6]);
+	}
+
+	#[test]
+	fn grow_in_place() {
+		let growths = Arc::new(Mutex::new(Vec::new()));
+		let mut arena = Arena::<Id, usize>::new();
+		let mut keys = Vec::new();
+
+		arena.set_realloc_hook({
+			let growths = Arc::clone(&growths);
+
+			move |old, new| growths.lock().unwrap().push((old, new))
+		});
+
+		for i in 0..100 {
+			arena.reserve(i % 3);
+
+			keys.push(arena.insert(i));
+
+			assert_eq!(arena.capacity(), arena.elements.len());
+			assert!(arena.len() <= arena.capacity());
+		}
+
+		for (i, &key) in keys.iter().enumerate() {
+			assert_eq!(key.index(), u32::try_from(i).unwrap());
+			assert_eq!(arena[key], i);
+		}
+
+		// Small reservations still grow geometrically, so the storage is only
+		// reallocated a logarithmic number of times.
+		let growths = growths.lock().unwrap();
+
+		assert!(growths.len() <= 6, "{growths:?}");
+		assert!(growths.iter().all(|&(old, new)| new >= old * 2));
+
+		let data = arena.elements.as_ptr();
+
+		arena.reserve(arena.capacity() - arena.len());
+
+		assert_eq!(arena.elements.as_ptr(), data);
+	}
+
+	#[test]
+	fn get_pin_mut() {
+		struct Pinned {
+			value: u32,
+			_pin: PhantomPinned,
+		}
+
+		let mut arena = Arena::<Id, Pin<Box<Pinned>>>::new();
+
+		let a = arena.insert(Box::pin(Pinned {
+			value: 10,
+			_pin: PhantomPinned,
+		}));
+
+		let address = core::ptr::from_ref::<Pinned>(&arena[a]);
+
+		arena.reserve(100);
+
+		let pinned = arena.get_pin_mut(a).unwrap();
+
+		assert_eq!(pinned.value, 10);
+		assert_eq!(core::ptr::from_ref::<Pinned>(&pinned), address);
+		assert_eq!(arena.remove(a).value, 10);
+		assert!(arena.get_pin_mut(a).is_none());
+	}
+
+	#[test]
+	#[cfg(debug_assertions)]
+	#[should_panic(expected = "should not be mutated while read-only")]
+	fn read_only_mutation() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		let a = arena.insert(10);
+
+		arena.set_read_only(true);
+
+		assert_eq!(arena.get(a), Some(&10));
+
+		let _b = arena.insert(20);
+	}
+
+	#[test]
+	#[cfg(debug_assertions)]
+	#[should_panic(expected = "should not be mutated while read-only")]
+	fn read_only_for_each_mut() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		let _a = arena.insert(10);
+
+		arena.set_read_only(true);
+		arena.for_each_mut(|_, value| *value += 1);
+	}
+
+	#[test]
+	#[cfg(debug_assertions)]
+	#[should_panic(expected = "should not be mutated while read-only")]
+	fn read_only_iter_mut() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		let _a = arena.insert(10);
+
+		arena.set_read_only(true);
+
+		for value in arena.values_mut() {
+			*value += 1;
+		}
+	}
+
+	#[test]
+	fn read_only_toggle() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		arena.set_read_only(true);
+
+		assert_eq!(arena.is_read_only(), cfg!(debug_assertions));
+
+		arena.set_read_only(false);
+
+		assert!(!arena.is_read_only());
+
+		let a = arena.insert(10);
+
+		arena[a] += 1;
+
+		assert_eq!(arena.remove(a), 11);
+	}
+
+	#[test]
+	fn structure_epoch() {
+		let mut arena = Arena::<Id, u32>::new();
+		let mut epoch = arena.structure_epoch();
+		let mut changed = |arena: &Arena<Id, u32>| {
+			let last = core::mem::replace(&mut epoch, arena.structure_epoch());
+
+			last != epoch
+		};
+
+		arena.reserve(8);
+
+		assert!(changed(&arena));
+
+		let a = arena.insert(10);
+
+		assert!(changed(&arena));
+
+		arena[a] += 1;
+
+		assert_eq!(arena.get(a), Some(&11));
+		assert!(!changed(&arena));
+
+		arena.remove(a);
+
+		assert!(changed(&arena));
+
+		let _b = arena.insert(20);
+
+		arena.clear();
+
+		assert!(changed(&arena));
+
+		arena.clear();
+
+		assert!(!changed(&arena));
+	}
+
+	#[test]
+	fn export_dense() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		let keys: Vec<_> = (0..5).map(|i| arena.insert(i * 10)).collect();
+
+		arena.remove(keys[1]);
+		arena.remove(keys[3]);
+
+		let (indices, values) = arena.export_dense();
+
+		assert_eq!(indices, [0, 2, 4]);
+		assert_eq!(values, [&0, &20, &40]);
+	}
+
+	#[test]
+	fn to_btree_map() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		let keys: Vec<_> = (0..5).map(|i| arena.insert(i * 10)).collect();
+
+		arena.remove(keys[2]);
+
+		let _key = arena.insert(50);
+		let map = arena.to_btree_map();
+
+		assert!(map.keys().copied().eq(arena.keys()));
+		assert!(map.values().eq(arena.values()));
+		assert_eq!(map.len(), 5);
+		assert_eq!(arena.into_btree_map(), map);
+	}
+
+	#[test]
+	fn realloc_hook() {
+		let growths = Arc::new(Mutex::new(Vec::new()));
+		let mut arena = Arena::<Id, u32>::new();
+
+		arena.set_realloc_hook({
+			let growths = Arc::clone(&growths);
+
+			move |old, new| growths.lock().unwrap().push((old, new))
+		});
+
+		for i in 0..20 {
+			let _key = arena.insert(i);
+		}
+
+		arena.reserve_exact(100);
+
+		let growths = growths.lock().unwrap();
+		let mut last = 0;
+
+		assert!(growths.len() > 2);
+
+		for &(old, new) in growths.iter() {
+			assert_eq!(old, last);
+			assert!(new > old);
+
+			last = new;
+		}
+
+		assert_eq!(last, arena.capacity());
+	}
+
+	#[test]
+	fn partition_values_mut() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		let keys: Vec<_> = (0..7).map(|i| arena.insert(i)).collect();
+
+		arena.remove(keys[3]);
+
+		let (even, odd) = arena.partition_values_mut(|key| key.index() % 2 == 0);
+
+		assert_eq!(even.len() + odd.len(), 6);
+
+		for value in even {
+			*value += 100;
+		}
+
+		for value in odd {
+			*value += 200;
+		}
+
+		assert!(arena.values().eq(&[100, 201, 102, 104, 205, 106]));
+	}
+
+	#[test]
+	fn insert_with_key() {
+		let mut arena = Arena::<Id<u8>, Id<u8>>::new();
+
+		let a = arena.insert_with_key(|key| key);
+
+		assert_eq!(arena[a], a);
+
+		let result = std::panic::catch_unwind(core::panic::AssertUnwindSafe(|| {
+			arena.insert_with_key(|_| panic!("construction failed"))
+		}));
+
+		assert!(result.is_err());
+		assert_eq!(arena.len(), 1);
+
+		let b = arena.insert_with_key(|key| key);
+
+		assert_eq!(b.index(), 1);
+		assert_eq!(arena[b], b);
+
+		while arena.try_insert_with_key(|key| key).is_some() {}
+
+		assert_eq!(arena.try_insert_with_key(|_| unreachable!()), None);
+		assert!(arena.iter().all(|(key, &value)| key == value));
+	}
+
+	#[test]
+	fn get_disjoint_mut() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		let a = arena.insert(10);
+		let b = arena.insert(20);
+		let c = arena.insert(30);
+
+		let [x, y, z] = arena.get_disjoint_mut([c, a, b]).unwrap();
+
+		core::mem::swap(x, y);
+		*z += 1;
+
+		assert_eq!(arena[a], 30);
+		assert_eq!(arena[b], 21);
+		assert_eq!(arena[c], 10);
+
+		assert!(arena.get_disjoint_mut([a, b, a]).is_none());
+		assert!(arena
+			.get_disjoint_mut([Id::new(100, a.version())])
+			.is_none());
+		assert!(arena.get_disjoint_mut::<0>([]).is_some());
+
+		arena.remove(b);
+
+		let d = arena.insert(40);
+
+		assert_eq!(d.index(), b.index());
+		assert!(arena.get_disjoint_mut([a, b]).is_none());
+		assert!(arena.get_disjoint_mut([b, d]).is_none());
+		assert_eq!(arena.get_disjoint_mut([a, d]), Some([&mut 30, &mut 40]));
+	}
+
+	#[test]
+	fn contains_key() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		let a = arena.insert(10);
+		let b = arena.insert(20);
+
+		assert!(arena.contains_key(a));
+		assert!(arena.contains_key(b));
+		assert!(!arena.contains_key(Id::new(2, a.version())));
+
+		arena.remove(b);
+
+		assert!(!arena.contains_key(b));
+
+		let c = arena.insert(30);
+
+		assert_eq!(c.index(), b.index());
+		assert!(arena.contains_key(c));
+		assert!(!arena.contains_key(b));
+	}
+
+	#[test]
+	fn clear_into() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		let keys: Vec<_> = (0..6).map(|i| arena.insert(i)).collect();
+		let mut pool = Vec::new();
+
+		arena.remove(keys[4]);
+		arena.clear_into(|value| pool.push(value));
+
+		assert_eq!(pool, [0, 1, 2, 3, 5]);
+		assert!(arena.is_empty());
+		assert!(keys.iter().all(|&key| arena.get(key).is_none()));
+
+		let fresh: Vec<_> = pool.drain(..).map(|value| arena.insert(value)).collect();
+
+		assert_eq!(arena.capacity(), 8);
+		assert!(fresh.iter().all(|&key| key.index() < 6));
+		assert!(fresh.iter().all(|&key| arena.contains_key(key)));
+	}
+
+	#[test]
+	fn stable_index() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		let a = arena.insert(10);
+		let b = arena.insert(20);
+		let position = arena.stable_index(b).unwrap();
+
+		assert_eq!(position, 1);
+
+		arena.remove(a);
+
+		let _c = arena.insert(30);
+		let _d = arena.insert(40);
+
+		assert_eq!(arena.stable_index(b), Some(position));
+		assert_eq!(arena.stable_index(a), None);
+
+		arena.remove(b);
+
+		assert_eq!(arena.stable_index(b), None);
+	}
+
+	#[test]
+	fn eq_and_hash() {
+		fn hash(arena: &Arena<Id, u32>) -> u64 {
+			let mut hasher = DefaultHasher::new();
+
+			arena.hash(&mut hasher);
+			hasher.finish()
+		}
+
+		let mut first = Arena::<Id, u32>::new();
+		let mut second = Arena::<Id, u32>::with_capacity(64);
+
+		for i in 0..4 {
+			let _key = first.insert(i);
+			let _key = second.insert(i);
+		}
+
+		let key = second.insert(4);
+
+		second.remove(key);
+
+		assert_ne!(first.capacity(), second.capacity());
+		assert_eq!(first, second);
+		assert_eq!(hash(&first), hash(&second));
+
+		let key = first.insert(5);
+
+		assert_ne!(first, second);
+		assert_ne!(hash(&first), hash(&second));
+
+		first.remove(key);
+
+		assert_eq!(first, second);
+		assert_eq!(hash(&first), hash(&second));
+
+		let mut third: Arena<Id, u32> = (0..4).collect();
+
+		third.remove(Id::new(3, NonZeroU32::MIN));
+
+		let _key = third.insert(3);
+
+		assert!(first.values().eq(third.values()));
+		assert_ne!(first, third);
+	}
+
+	#[test]
+	fn make_key() {
+		let mut arena = Arena::<Id, u32>::with_capacity(4);
+
+		let a = arena.insert(10);
+
+		assert_eq!(arena.make_key(0, NonZeroU32::MIN), Some(a));
+		assert_eq!(
+			arena.make_key(3, NonZeroU32::MIN),
+			Some(Id::new(3, NonZeroU32::MIN))
+		);
+		assert_eq!(arena.make_key(4, NonZeroU32::MIN), None);
+		assert_eq!(arena.make_key(usize::MAX, NonZeroU32::MIN), None);
+	}
+
+	#[test]
+	fn get_key_value() {
+		let mut arena = Arena::<Id, u32>::with_capacity(4);
+
+		let a = arena.insert(10);
+		let b = arena.insert(20);
+
+		assert_eq!(arena.get_key_value(a), Some((a, &10)));
+
+		arena.remove(b);
+
+		assert_eq!(arena.get_key_value(b), None);
+		assert_eq!(arena.get_key_value(Id::new(3, NonZeroU32::MIN)), None);
+		assert_eq!(arena.get_key_value(Id::new(9, NonZeroU32::MIN)), None);
+
+		let c = arena.insert(30);
+
+		assert_eq!(c.index(), b.index());
+		assert_eq!(arena.get_key_value(b), Some((c, &30)));
+	}
+
+	#[test]
+	fn keys_checksum() {
+		let mut first = Arena::<Id, u32>::new();
+		let mut second = Arena::<Id, u32>::new();
+
+		let keys: Vec<_> = (0..4).map(|i| first.insert(i)).collect();
+
+		for i in (0..4).rev() {
+			let _key = second.insert(i * 10);
+		}
+
+		assert_eq!(first.keys_checksum(), second.keys_checksum());
+
+		first.remove(keys[2]);
+
+		assert_ne!(first.keys_checksum(), second.keys_checksum());
+
+		let reused = first.insert(2);
+
+		assert_eq!(reused.index(), keys[2].index());
+		assert_ne!(first.keys_checksum(), second.keys_checksum());
+		assert_eq!(Arena::<Id, u32>::new().keys_checksum(), 0);
+	}
+
+	#[test]
+	fn shrink_to_fit() {
+		let mut arena = Arena::<Id, usize>::new();
+
+		let keys: Vec<_> = (0..100).map(|i| arena.insert(i)).collect();
+
+		for &key in &keys[9..] {
+			arena.remove(key);
+		}
+
+		arena.remove(keys[3]);
+
+		let tomb = keys[8];
+		let _tomb = arena.remove_tombstoned(tomb);
+
+		arena.shrink_to_fit();
+
+		assert_eq!(arena.capacity(), 9);
+		assert_eq!(arena.len(), 7);
+		assert_eq!(arena.get(keys[3]), None);
+		assert_eq!(arena.get_tombstone(tomb), Some(&8));
+		assert!(keys[..8]
+			.iter()
+			.enumerate()
+			.filter(|&(i, _)| i != 3)
+			.all(|(i, &key)| arena[key] == i));
+
+		let a = arena.insert(30);
+
+		assert_eq!(a.index(), 3);
+
+		arena.clear();
+		arena.shrink_to_fit();
+
+		assert_eq!(arena.capacity(), 9);
+
+		arena.purge_tombstones();
+		arena.shrink_to_fit();
+
+		assert_eq!(arena.capacity(), 0);
+	}
+
+	#[test]
+	fn shrink_keeps_stale_keys_invalid() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		let a = arena.insert(0);
+		let first = arena.insert(1);
+
+		arena.remove(first);
+
+		let second = arena.insert(2);
+
+		arena.remove(second);
+		arena.shrink_to_fit();
+
+		assert_eq!(arena.capacity(), 1);
+
+		let b = arena.insert(3);
+
+		assert_eq!(b.index(), first.index());
+		assert_ne!(b, first);
+		assert_ne!(b, second);
+		assert_eq!(arena.get(first), None);
+		assert_eq!(arena.get(second), None);
+		assert_eq!(arena[a], 0);
+		assert_eq!(arena[b], 3);
+	}
+
+	#[test]
+	fn shrink_to() {
+		let mut arena = Arena::<Id, usize>::new();
+
+		let keys: Vec<_> = (0..20).map(|i| arena.insert(i)).collect();
+		let capacity = arena.capacity();
+
+		arena.shrink_to(capacity + 10);
+
+		assert_eq!(arena.capacity(), capacity);
+
+		for &key in &keys[2..] {
+			arena.remove(key);
+		}
+
+		arena.shrink_to(6);
+
+		assert_eq!(arena.capacity(), 6);
+		assert_eq!([arena[keys[0]], arena[keys[1]]], [0, 1]);
+
+		let a = arena.insert(2);
+
+		assert_eq!(a.index(), 2);
+		assert_eq!(arena.get(keys[2]), None);
+
+		arena.remove(keys[0]);
+		arena.shrink_to(1);
+
+		assert_eq!(arena.capacity(), 3);
+		assert_eq!(arena.insert(0).index(), 0);
+		assert_eq!(arena.insert(3).index(), 3);
+	}
+
+	#[test]
+	fn with_mut() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		let a = arena.insert(10);
+		let b = arena.insert(20);
+
+		assert_eq!(
+			arena.with_mut(a, |value| {
+				*value += 1;
+				*value * 2
+			}),
+			Some(22)
+		);
+
+		arena.remove(b);
+
+		let mut called = false;
+
+		assert_eq!(arena.with_mut(b, |_| called = true), None);
+		assert!(!called);
+		assert_eq!(arena[a], 11);
+	}
+
+	#[test]
+	fn append() {
+		let mut first = Arena::<Id, u32>::new();
+		let mut second = Arena::<Id, u32>::new();
+
+		let a = first.insert(10);
+		let b = first.insert(20);
+
+		first.remove(a);
+
+		let keys: Vec<_> = (0..3).map(|i| second.insert(i)).collect();
+
+		second.remove(keys[1]);
+
+		let remap = first.append(&mut second);
+
+		assert_eq!(first.len(), 3);
+		assert!(second.is_empty());
+		assert_eq!(remap.len(), 2);
+		assert_eq!(first[b], 20);
+
+		for (old, new) in remap {
+			assert_eq!(first[new], old.index());
+		}
+
+		assert_eq!(second.insert(30).index(), keys[2].index());
+		assert_eq!(first.insert(40).index(), 3);
+	}
+
+	#[test]
+	fn reserve_after_remove() {
+		let mut arena = Arena::<Id, usize>::with_capacity(4);
+
+		let keys: Vec<_> = (0..4).map(|i| arena.insert(i)).collect();
+
+		arena.remove(keys[1]);
+		arena.reserve(20);
+
+		let inserted: Vec<_> = (0..20).map(|i| arena.insert(10 + i)).collect();
+
+		assert_eq!(inserted[0].index(), keys[1].index());
+		assert!(inserted[1..].iter().map(|key| key.index()).eq(4..23));
+
+		assert!(inserted
+			.iter()
+			.enumerate()
+			.all(|(i, &key)| arena[key] == 10 + i));
+
+		assert_eq!(arena.len(), 23);
+		assert_eq!(arena.get(keys[1]), None);
+		assert_eq!([arena[keys[0]], arena[keys[2]], arena[keys[3]]], [0, 2, 3]);
+	}
+
+	#[test]
+	fn retain_categorized() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		let keys: Vec<_> = (0..6).map(|i| arena.insert(i)).collect();
+		let mut removed = Vec::new();
+
+		arena.retain_categorized(
+			|_, &value| match value {
+				0 | 3 => Err("triple"),
+				4 => Err("four"),
+				_ => Ok(()),
+			},
+			|key, reason| removed.push((key, reason)),
+		);
+
+		assert_eq!(
+			removed,
+			[(keys[0], "triple"), (keys[3], "triple"), (keys[4], "four")]
+		);
+
+		assert!(arena.values().copied().eq([1, 2, 5]));
+	}
+
+	#[test]
+	fn try_reserve() {
+		let mut arena = Arena::<Id<usize, NonZeroU64>, [u8; 64]>::new();
+
+		let a = arena.insert([1; 64]);
+		let capacity = arena.capacity();
+		let epoch = arena.structure_epoch();
+
+		assert!(arena.try_reserve(usize::MAX).is_err());
+		assert!(arena.try_reserve_exact(usize::MAX / 2).is_err());
+		assert_eq!(arena.len(), 1);
+		assert_eq!(arena.capacity(), capacity);
+		assert_eq!(arena.structure_epoch(), epoch);
+
+		assert!(arena.try_reserve_exact(10).is_ok());
+		assert_eq!(arena.capacity(), 11);
+		assert_eq!(arena[a], [1; 64]);
+		assert_eq!(arena.insert([2; 64]).index(), 1);
+	}
+
+	#[test]
+	fn tagged_key() {
+		#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+		struct TaggedKey {
+			tag: u8,
+			index: u32,
+			version: NonZeroU32,
+		}
+
+		impl TaggedKey {
+			const fn with_tag(self, tag: u8) -> Self {
+				Self { tag, ..self }
+			}
+		}
+
+		impl Referent for TaggedKey {
+			type Index = u32;
+			type Version = NonZeroU32;
+
+			fn new(index: u32, version: NonZeroU32) -> Self {
+				Self {
+					tag: 0,
+					index,
+					version,
+				}
+			}
+
+			fn index(self) -> u32 {
+				self.index
+			}
+
+			fn version(self) -> NonZeroU32 {
+				self.version
+			}
+		}
+
+		let mut arena = Arena::<TaggedKey, &str>::new();
+
+		let _a = arena.insert("a");
+		let b = arena.insert("b").with_tag(7);
+
+		assert_eq!(b.tag, 7);
+		assert_eq!(arena[b], "b");
+		assert_eq!(arena.get(b.with_tag(3)), Some(&"b"));
+
+		let (key, _) = arena.get_key_value(b).unwrap();
+
+		assert_eq!(key.with_tag(b.tag), b);
+		assert_eq!(arena.remove(b), "b");
+		assert_eq!(arena.get(b.with_tag(0)), None);
+	}
 
 	#[test]
-	fn add_and_remove() {
+	fn swap() {
 		let mut arena = Arena::<Id, u32>::new();
 
 		let a = arena.insert(10);
 		let b = arena.insert(20);
 		let c = arena.insert(30);
 
-		assert_eq!(arena[a], 10);
+		assert!(arena.swap(a, c));
+		assert_eq!([arena[a], arena[b], arena[c]], [30, 20, 10]);
+		assert!(arena.swap(b, b));
 		assert_eq!(arena[b], 20);
-		assert_eq!(arena[c], 30);
 
-		assert_eq!(arena.len(), 3);
+		arena.remove(b);
 
-		arena.remove(a);
+		let d = arena.insert(40);
 
-		assert_eq!(arena.len(), 2);
+		assert!(!arena.swap(a, b));
+		assert!(!arena.swap(b, d));
+		assert!(!arena.swap(b, b));
+		assert_eq!([arena[a], arena[d]], [30, 40]);
+	}
+
+	#[test]
+	fn get_all() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		let a = arena.insert(10);
+		let b = arena.insert(20);
+		let c = arena.insert(30);
 
 		arena.remove(b);
 
-		assert_eq!(arena.len(), 1);
+		let d = arena.insert(40);
 
-		arena.remove(c);
+		assert!(arena.get_all([c, b, a, d, a]).eq([
+			(c, Some(&30)),
+			(b, None),
+			(a, Some(&10)),
+			(d, Some(&40)),
+			(a, Some(&10))
+		]));
+	}
 
-		assert_eq!(arena.len(), 0);
+	#[test]
+	fn clone_from() {
+		let mut source = Arena::<Id, String>::new();
+
+		let keys: Vec<_> = (0..6).map(|i| source.insert(i.to_string())).collect();
+
+		source.remove(keys[1]);
+		source.remove(keys[4]);
+
+		let mut target = Arena::<Id, String>::with_capacity(64);
+
+		for _ in 0..3 {
+			let key = target.insert("old".into());
+
+			target.remove(key);
+		}
+
+		target.clone_from(&source);
+
+		assert_eq!(target.capacity(), 64);
+		assert_eq!(target, source);
+		assert!(source
+			.elements
+			.iter()
+			.zip(&target.elements)
+			.all(|(a, b)| a.version() == b.version()));
+
+		let mut copy = source.clone();
+
+		for value in ["a", "b", "c", "d"] {
+			let key = target.insert(value.into());
+
+			assert_eq!(key, copy.insert(value.into()));
+		}
+
+		target.clone_from(&Arena::new());
+
+		assert_eq!(target.capacity(), 64);
+		assert!(target.is_empty());
+		assert_eq!(target.insert("e".into()).index(), 0);
 	}
 
 	#[test]
-	fn remove_twice() {
+	fn set_capacity() {
+		let mut arena = Arena::<Id<u8>, u32>::new();
+
+		assert_eq!(arena.set_capacity(10), Ok(()));
+		assert_eq!(arena.capacity(), 10);
+
+		let keys: Vec<_> = (0..6).map(|i| arena.insert(i)).collect();
+
+		arena.remove(keys[5]);
+		arena.remove(keys[2]);
+
+		assert_eq!(arena.set_capacity(5), Ok(()));
+		assert_eq!(arena.capacity(), 5);
+		assert_eq!(
+			arena.set_capacity(4),
+			Err(CapacityError::Occupied { required: 5 })
+		);
+		assert_eq!(arena.set_capacity(300), Err(CapacityError::Unrepresentable));
+		assert_eq!(arena.capacity(), 5);
+		assert_eq!(arena.set_capacity(8), Ok(()));
+		assert_eq!(arena.capacity(), 8);
+		assert_eq!(arena.insert(10).index(), 2);
+
+		let reused = arena.insert(20);
+
+		assert_eq!(reused.index(), 5);
+		assert_ne!(reused, keys[5]);
+		assert_eq!(arena.get(keys[5]), None);
+		assert_eq!(arena[keys[4]], 4);
+	}
+
+	#[test]
+	fn set_capacity_past_unused_slots() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		assert_eq!(arena.set_capacity(4), Ok(()));
+
+		let keys: Vec<_> = (0..4).map(|i| arena.insert(i)).collect();
+
+		let _tomb = arena.remove_tombstoned(keys[0]);
+		let _tomb = arena.remove_tombstoned(keys[1]);
+
+		assert_eq!(arena.set_capacity(4), Ok(()));
+		assert_eq!(arena.capacity(), 4);
+		assert_eq!(arena.set_capacity(5), Ok(()));
+		assert_eq!(arena.capacity(), 5);
+
+		let mut arena = Arena::<Id, u32>::new();
+
+		arena.set_quarantine(2);
+
+		let keys: Vec<_> = (0..4).map(|i| arena.insert(i)).collect();
+
+		arena.remove(keys[1]);
+		arena.remove(keys[2]);
+
+		assert_eq!(arena.quarantined(), 2);
+		assert_eq!(arena.set_capacity(6), Ok(()));
+		assert_eq!(arena.capacity(), 6);
+		assert_eq!(arena.quarantined(), 2);
+	}
+
+	#[test]
+	fn bounded_id() {
+		let mut arena = Arena::<BoundedId<8>, usize>::new();
+
+		let keys: Vec<_> = (0..8).map(|i| arena.insert(i)).collect();
+
+		assert!(arena.is_full());
+		assert_eq!(arena.capacity(), 8);
+		assert_eq!(arena.try_insert(8), None);
+		assert_eq!(arena.capacity(), 8);
+
+		arena.remove(keys[3]);
+
+		assert!(!arena.is_full());
+
+		let a = arena.insert(30);
+
+		assert_eq!(a.index().try_into_unchecked(), 3);
+		assert!(arena.is_full());
+
+		let arena = Arena::<BoundedId<8>, usize>::with_capacity(100);
+
+		assert_eq!(arena.capacity(), 8);
+		assert!(!arena.is_full());
+	}
+
+	#[test]
+	fn reset() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		let keys: Vec<_> = (0..6).map(|i| arena.insert(i)).collect();
+
+		arena.remove(keys[2]);
+
+		let reused = arena.insert(20);
+		let _tomb = arena.remove_tombstoned(keys[4]);
+		let capacity = arena.capacity();
+
+		arena.reset();
+
+		assert!(arena.is_empty());
+		assert_eq!(arena.capacity(), capacity);
+		assert_eq!(arena.get(reused), None);
+		assert_eq!(arena.get_tombstone(keys[4]), None);
+		assert!(arena
+			.elements
+			.iter()
+			.all(|element| element.version() == NonZeroU32::MIN));
+
+		let fresh: Vec<_> = (0..capacity).map(|i| arena.insert(i as u32 * 10)).collect();
+
+		assert_eq!(fresh[..6], keys);
+		assert_eq!(arena[keys[2]], 20);
+		assert!(fresh
+			.iter()
+			.enumerate()
+			.all(|(i, key)| key.index() as usize == i));
+	}
+
+	#[test]
+	fn value_stats() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		assert_eq!(arena.value_stats(), None);
+
+		let keys: Vec<_> = [2, 4, 4, 4, 100, 5, 5, 7, 9]
+			.into_iter()
+			.map(|value| arena.insert(value))
+			.collect();
+
+		arena.remove(keys[4]);
+
+		let stats = arena.value_stats().unwrap();
+
+		assert_eq!(stats.count, 8);
+		assert!((stats.mean - 5.0).abs() < 1e-12);
+		assert!((stats.variance - 4.0).abs() < 1e-12);
+
+		arena.clear();
+
+		assert_eq!(arena.value_stats(), None);
+	}
+
+	#[test]
+	fn quarantine() {
 		let mut arena = Arena::<Id, usize>::new();
 
+		arena.set_quarantine(3);
+
+		let keys: Vec<_> = (0..8).map(|i| arena.insert(i)).collect();
+
+		for &key in &keys[..3] {
+			arena.remove(key);
+		}
+
+		assert_eq!(arena.quarantined(), 3);
+
 		let a = arena.insert(10);
 
-		assert_eq!(arena.try_remove(a), Some(10));
+		assert_eq!(a.index(), 8);
+
+		arena.remove(keys[3]);
+
+		let b = arena.insert(20);
+		let c = arena.insert(30);
+
+		assert_eq!(b.index(), keys[0].index());
+		assert_ne!(b, keys[0]);
+		assert!(c.index() >= 9);
+
+		arena.set_quarantine(0);
+
+		let d = arena.insert(40);
+		let e = arena.insert(50);
+
+		assert_eq!(d.index(), keys[3].index());
+		assert_eq!(e.index(), keys[2].index());
+		assert_eq!(arena.quarantined(), 0);
+	}
+
+	#[test]
+	fn quarantine_bulk_removals() {
+		let mut arena = Arena::<Id, usize>::new();
+
+		arena.set_quarantine(4);
+
+		let keys: Vec<_> = (0..4).map(|i| arena.insert(i)).collect();
+
+		arena.retain(|_, &value| value != 1);
+
+		assert_eq!(arena.quarantined(), 1);
+		assert_ne!(arena.insert(10).index(), keys[1].index());
+
+		arena.clear();
+
+		assert_eq!(arena.quarantined(), 4);
+		assert_eq!(arena.insert(20).index(), keys[1].index());
+
+		let mut arena = Arena::<Id, usize>::new();
+
+		arena.set_quarantine(4);
+
+		let keys: Vec<_> = (0..4).map(|i| arena.insert(i)).collect();
+		let drained: Result<Vec<_>, ()> = arena.try_drain(|_, value| Ok(value));
+
+		assert_eq!(drained, Ok(vec![0, 1, 2, 3]));
+		assert_eq!(arena.quarantined(), 4);
+		assert_eq!(arena.insert(10).index(), 4);
+
+		arena.clear_into(drop);
+
+		assert_eq!(arena.quarantined(), 4);
+		assert_eq!(arena.insert(20).index(), keys[0].index());
+
+		let mut arena = Arena::<Id, usize>::new();
+
+		arena.set_quarantine(4);
+
+		let keys: Vec<_> = (0..4).map(|i| arena.insert(i)).collect();
+
+		let _tomb = arena.remove_tombstoned(keys[2]);
+		arena.purge_tombstones();
+
+		assert_eq!(arena.quarantined(), 1);
+		assert_ne!(arena.insert(10).index(), keys[2].index());
+	}
+
+	#[test]
+	fn reserve_past_quarantine() {
+		let mut arena = Arena::<Id, usize>::with_capacity(4);
+
+		arena.set_quarantine(4);
+
+		let keys: Vec<_> = (0..4).map(|i| arena.insert(i)).collect();
+
+		arena.remove(keys[0]);
+		arena.remove(keys[1]);
+		arena.reserve(2);
+
+		assert_eq!(arena.quarantined(), 2);
+		assert!(arena.insert_within_capacity(10).is_ok());
+		assert!(arena.insert_within_capacity(20).is_ok());
+	}
+
+	#[test]
+	fn shrink_keeps_quarantine() {
+		let mut arena = Arena::<Id, usize>::new();
+
+		arena.set_quarantine(2);
+
+		let keys: Vec<_> = (0..4).map(|i| arena.insert(i)).collect();
+
+		arena.remove(keys[1]);
+		arena.remove(keys[3]);
+		arena.shrink_to_fit();
+
+		assert_eq!(arena.capacity(), 3);
+		assert_eq!(arena.quarantined(), 1);
+
+		let a = arena.insert(10);
+
+		assert_ne!(a.index(), keys[1].index());
+
+		arena.remove(keys[0]);
+		arena.remove(keys[2]);
+
+		assert_eq!(arena.quarantined(), 2);
+		assert_eq!(arena.insert(20).index(), keys[1].index());
+	}
+
+	#[test]
+	fn insert_past_tombstones() {
+		let mut arena = Arena::<Id, usize>::with_capacity(4);
+
+		let keys: Vec<_> = (0..4).map(|i| arena.insert(i)).collect();
+		let _tomb = arena.remove_tombstoned(keys[0]);
+
+		let a = arena.insert(10);
+
+		assert_eq!(a.index(), 4);
+		assert_eq!(arena.len(), 4);
+	}
+
+	#[test]
+	fn reserve_past_tombstones() {
+		let mut arena = Arena::<Id, usize>::with_capacity(4);
+
+		let keys: Vec<_> = (0..4).map(|i| arena.insert(i)).collect();
+		let _tomb = arena.remove_tombstoned(keys[0]);
+		let _tomb = arena.remove_tombstoned(keys[1]);
+
+		arena.reserve(2);
+
+		assert!(arena.insert_within_capacity(10).is_ok());
+		assert!(arena.insert_within_capacity(20).is_ok());
+
+		arena.reserve_exact(1);
+
+		assert!(arena.insert_within_capacity(30).is_ok());
+	}
+
+	#[test]
+	fn remove_stale() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		let a = arena.insert(10);
+		let _b = arena.insert(20);
+
+		arena.remove(a);
+
+		let c = arena.insert(30);
+
 		assert_eq!(arena.try_remove(a), None);
+		assert_eq!(arena[c], 30);
 	}
 
 	#[test]
-	fn remove_twice_nil() {
-		let mut arena = Arena::<Id<u32, Nil>, usize>::new();
+	fn remove_twice_nil_not_empty() {
+		let mut arena = Arena::<Id<u32, Nil>, u32>::new();
 
 		let a = arena.insert(10);
+		let b = arena.insert(20);
 
 		assert_eq!(arena.try_remove(a), Some(10));
 		assert_eq!(arena.try_remove(a), None);
+		assert_eq!(arena.len(), 1);
+		assert_eq!(arena[b], 20);
+
+		let c = arena.insert(30);
+
+		assert_eq!(c, a);
+		assert_eq!(arena.len(), 2);
 	}
 
 	#[test]
-	fn add_and_clear() {
+	fn remove_versioned() {
 		let mut arena = Arena::<Id, u32>::new();
 
 		let a = arena.insert(10);
-		let b = arena.insert(20);
-		let c = arena.insert(30);
+		let (value, version) = arena.remove_versioned(a).unwrap();
 
-		assert_eq!(arena[a], 10);
-		assert_eq!(arena[b], 20);
-		assert_eq!(arena[c], 30);
+		assert_eq!(value, 10);
+		assert_eq!(version.get(), a.version().get() + 1);
+		assert_eq!(arena.insert(20).version(), version);
+		assert_eq!(arena.remove_versioned(a), None);
+	}
 
-		assert_eq!(arena.len(), 3);
+	/// Drives an [`Arena`] and a `HashMap` keyed by index and version through the
+	/// same random operations, checking that they agree after every step.
+	///
+	/// To check the harness itself, reintroduce an off-by-one in `try_remove`,
+	/// such as looking up `key.index() + 1` or subtracting 2 from `len`. Every
+	/// `model_*` test then fails on its first seed.
+	fn check_model<Key: Referent>(seed: u64, steps: usize) {
+		fn version_of<Key: Referent>(key: Key) -> u64 {
+			key.version().try_into_unchecked().get()
+		}
 
-		arena.clear();
+		fn is_exhausted<Key: Referent>(key: Key) -> bool {
+			NonZeroU64::new(version_of(key) + 1)
+				.and_then(Key::Version::try_from_checked)
+				.is_none()
+		}
 
-		assert_eq!(arena.len(), 0);
+		let mut state = seed;
+		let mut random = move |bound: u64| {
+			state ^= state << 13;
+			state ^= state >> 7;
+			state ^= state << 17;
 
-		assert_eq!(arena.get(a), None);
-		assert_eq!(arena.get(b), None);
-		assert_eq!(arena.get(c), None);
+			state % bound
+		};
+
+		let mut arena = Arena::<Key, u64>::new();
+		let mut model = HashMap::new();
+		let mut issued = Vec::new();
+
+		// Favor recent keys, so that slots are recycled often enough to saturate.
+		let pick = |issued: &[Key], random: &mut dyn FnMut(u64) -> u64| {
+			let len = issued.len() as u64;
+			let position = if random(4) == 0 {
+				random(len)
+			} else {
+				len - 1 - random(len.min(4))
+			};
+
+			issued[usize::try_from(position).unwrap()]
+		};
+
+		for step in 0..steps {
+			match random(100) {
+				0..45 => {
+					let value = step as u64;
+					let limit = Key::Index::MAX.try_into_unchecked();
+
+					if let Some(key) = arena.try_insert(value) {
+						let slot = (key.index().try_into_unchecked(), version_of(key));
+
+						assert_eq!(model.insert(slot, value), None, "key handed out twice");
+
+						issued.push(key);
+					} else {
+						assert_eq!(model.len(), limit, "insertion failed below capacity");
+					}
+				}
+				45..75 if !issued.is_empty() => {
+					let key = pick(&issued, &mut random);
+					let slot = (key.index().try_into_unchecked(), version_of(key));
+					let expected = if is_exhausted(key) {
+						None
+					} else {
+						model.remove(&slot)
+					};
+
+					assert_eq!(arena.try_remove(key), expected);
+				}
+				75..97 if !issued.is_empty() => {
+					let key = pick(&issued, &mut random);
+					let slot = (key.index().try_into_unchecked(), version_of(key));
+
+					assert_eq!(arena.get(key), model.get(&slot));
+				}
+				97..99 => {
+					let salt = random(3) + 2;
+
+					arena.retain(|_, &value| !value.is_multiple_of(salt));
+					model.retain(|&(index, version), &mut value| {
+						let index = Key::Index::try_from_checked(index).unwrap();
+						let version =
+							Key::Version::try_from_checked(NonZeroU64::new(version).unwrap());
+
+						is_exhausted(Key::new(index, version.unwrap()))
+							|| !value.is_multiple_of(salt)
+					});
+				}
+				99 => {
+					arena.clear();
+					model.retain(|&(index, version), _| {
+						let index = Key::Index::try_from_checked(index).unwrap();
+						let version =
+							Key::Version::try_from_checked(NonZeroU64::new(version).unwrap());
+
+						is_exhausted(Key::new(index, version.unwrap()))
+					});
+				}
+				_ => {}
+			}
+
+			assert_eq!(arena.len(), model.len());
+		}
+
+		for key in issued {
+			let slot = (key.index().try_into_unchecked(), version_of(key));
+
+			assert_eq!(arena.get(key), model.get(&slot));
+		}
+	}
+
+	#[test]
+	fn model_generational() {
+		for seed in 1..=16 {
+			check_model::<Id>(seed, 4000);
+		}
+	}
+
+	#[test]
+	fn model_nil() {
+		for seed in 1..=16 {
+			check_model::<Id<u32, Nil>>(seed, 4000);
+		}
+	}
+
+	#[test]
+	fn model_saturation() {
+		for seed in 1..=16 {
+			check_model::<Id<u8, NonZeroU8>>(seed, 60000);
+		}
 	}
 }
@@ -0,0 +1,111 @@
+use core::num::NonZeroU64;
+
+use crate::{
+	collection::Arena,
+	element::Element,
+	referent::{Referent, Similar},
+};
+
+/// How a key resolves in an [`Arena`].
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum KeyState {
+	/// The slot is occupied with the same version as the key.
+	Live,
+	/// The slot is occupied with a different version than the key.
+	Stale,
+	/// The slot is vacant.
+	Vacant,
+	/// The slot holds a tombstone left by [`Arena::remove_tombstoned`].
+	Tombstone,
+	/// The index of the key is past the capacity of the [`Arena`].
+	OutOfRange,
+}
+
+/// A description of the slot a key refers to, for debugging failed lookups.
+///
+/// Created by the [`Arena::describe_key`] method.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct KeyDescription {
+	/// The index of the key.
+	pub index: usize,
+	/// The version of the key.
+	pub version: NonZeroU64,
+	/// The current version of the slot, if it exists.
+	pub slot_version: Option<NonZeroU64>,
+	/// How the key resolves.
+	pub state: KeyState,
+}
+
+impl<Key: Referent, Value> Arena<Key, Value> {
+	/// Returns a description of the slot the key refers to and how it resolves.
+	#[must_use]
+	pub fn describe_key(&self, key: Key) -> KeyDescription {
+		let index = key.index().try_into_unchecked();
+		let version = key.version().try_into_unchecked();
+		let element = self.elements.get(index);
+		let slot_version = element.map(|element| element.version().try_into_unchecked());
+
+		let state = match element {
+			Some(Element::Occupied { .. }) if slot_version == Some(version) => KeyState::Live,
+			Some(Element::Occupied { .. }) => KeyState::Stale,
+			Some(Element::Vacant { .. }) => KeyState::Vacant,
+			Some(Element::Tombstone { .. }) => KeyState::Tombstone,
+			None => KeyState::OutOfRange,
+		};
+
+		KeyDescription {
+			index,
+			version,
+			slot_version,
+			state,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use core::num::NonZeroU64;
+
+	use crate::{
+		collection::Arena,
+		diagnostics::{KeyDescription, KeyState},
+		referent::Id,
+	};
+
+	#[test]
+	fn describe_key() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		let a = arena.insert(10);
+
+		arena.remove(a);
+
+		let b = arena.insert(20);
+		let two = NonZeroU64::new(2);
+
+		assert_eq!(
+			arena.describe_key(b),
+			KeyDescription {
+				index: 0,
+				version: two.unwrap(),
+				slot_version: two,
+				state: KeyState::Live,
+			}
+		);
+
+		assert_eq!(
+			arena.describe_key(a),
+			KeyDescription {
+				index: 0,
+				version: NonZeroU64::MIN,
+				slot_version: two,
+				state: KeyState::Stale,
+			}
+		);
+
+		let description = arena.describe_key(Id::dangling());
+
+		assert_eq!(description.state, KeyState::OutOfRange);
+		assert_eq!(description.slot_version, None);
+	}
+}
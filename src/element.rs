@@ -2,10 +2,13 @@ use core::num::NonZeroU64;
 
 use crate::referent::{try_transform, Similar};
 
+// `Occupied` comes first so that the common case of a successful lookup checks
+// for the zero discriminant. Nothing else depends on the order of the variants.
 #[derive(Debug, Clone)]
 pub enum Element<Version, Index, Value> {
 	Occupied { version: Version, value: Value },
 	Vacant { version: Version, next: Index },
+	Tombstone { version: Version, value: Value },
 }
 
 impl<Version, Index, Value> Element<Version, Index, Value>
@@ -15,10 +18,16 @@ where
 {
 	pub const fn version(&self) -> Version {
 		match *self {
-			Self::Occupied { version, .. } | Self::Vacant { version, .. } => version,
+			Self::Occupied { version, .. }
+			| Self::Vacant { version, .. }
+			| Self::Tombstone { version, .. } => version,
 		}
 	}
 
+	pub fn is_exhausted(&self) -> bool {
+		try_transform(self.version(), |version| version.checked_add(1)).is_none()
+	}
+
 	pub const fn as_ref(&self) -> Option<&Value> {
 		if let Self::Occupied { value, .. } = self {
 			Some(value)
@@ -98,8 +107,73 @@ where
 
 				unreachable!("`Element::reset` called on vacant element")
 			}
+			Self::Tombstone { version, value } => {
+				*self = Self::Tombstone { version, value };
+
+				unreachable!("`Element::reset` called on tombstone element")
+			}
+		}
+	}
+
+	pub fn tombstone(&self, parameter: Version) -> Option<&Value> {
+		if let Self::Tombstone { version, value } = self {
+			let parameter = parameter.try_into_unchecked();
+			let version = version.try_into_unchecked();
+
+			(parameter == version).then_some(value)
+		} else {
+			None
+		}
+	}
+
+	pub fn entomb(&mut self) -> bool {
+		let tombstone = Self::Vacant {
+			version: Version::MAX,
+			next: Index::MAX,
+		};
+
+		match core::mem::replace(self, tombstone) {
+			Self::Occupied { version, value } => {
+				let bumped = try_transform(version, |version| version.checked_add(1)).is_some();
+
+				*self = if bumped {
+					Self::Tombstone { version, value }
+				} else {
+					Self::Occupied { version, value }
+				};
+
+				bumped
+			}
+			element => {
+				*self = element;
+
+				false
+			}
+		}
+	}
+
+	pub fn purge(&mut self, next: Index) -> Option<Value> {
+		let tombstone = Self::Vacant {
+			version: Version::MAX,
+			next: Index::MAX,
+		};
+
+		match core::mem::replace(self, tombstone) {
+			Self::Tombstone { version, value } => {
+				let version = try_transform(version, |version| version.checked_add(1))
+					.unwrap_or_else(|| unreachable!());
+
+				*self = Self::Vacant { version, next };
+
+				Some(value)
+			}
+			element => {
+				*self = element;
+
+				None
+			}
 		}
 	}
 }
 
-pub type List<Version, Index, Value> = alloc::boxed::Box<[Element<Version, Index, Value>]>;
+pub type List<Version, Index, Value> = alloc::vec::Vec<Element<Version, Index, Value>>;
@@ -0,0 +1,345 @@
+use crate::{
+	collection::Arena,
+	element::Element,
+	referent::{try_transform, Referent, Similar},
+};
+
+/// A view into a single slot of an [`Arena`], addressed by its raw index.
+///
+/// Created by the [`Arena::slot_entry`] method.
+pub enum SlotEntry<'a, Key: Referent, Value> {
+	/// An occupied slot.
+	Occupied(OccupiedSlot<'a, Key, Value>),
+	/// A vacant slot.
+	Vacant(VacantSlot<'a, Key, Value>),
+}
+
+/// A view into an occupied slot of an [`Arena`].
+pub struct OccupiedSlot<'a, Key: Referent, Value> {
+	arena: &'a mut Arena<Key, Value>,
+	index: Key::Index,
+}
+
+/// A view into a vacant slot of an [`Arena`].
+pub struct VacantSlot<'a, Key: Referent, Value> {
+	arena: &'a mut Arena<Key, Value>,
+	index: Key::Index,
+}
+
+impl<'a, Key: Referent, Value> OccupiedSlot<'a, Key, Value> {
+	fn element(&self) -> &Element<Key::Version, Key::Index, Value> {
+		&self.arena.elements[self.index.try_into_unchecked()]
+	}
+
+	/// Returns the key of the slot.
+	#[inline]
+	#[must_use]
+	pub fn key(&self) -> Key {
+		Key::new(self.index, self.element().version())
+	}
+
+	/// Returns a reference to the value in the slot.
+	#[inline]
+	#[must_use]
+	pub fn get(&self) -> &Value {
+		self.element().as_ref().unwrap_or_else(|| unreachable!())
+	}
+
+	/// Returns a mutable reference to the value in the slot.
+	#[inline]
+	#[must_use]
+	pub fn get_mut(&mut self) -> &mut Value {
+		self.arena.elements[self.index.try_into_unchecked()]
+			.as_mut()
+			.unwrap_or_else(|| unreachable!())
+	}
+
+	/// Returns a mutable reference to the value in the slot, with the
+	/// lifetime of the [`Arena`].
+	#[inline]
+	#[must_use]
+	pub fn into_mut(self) -> &'a mut Value {
+		self.arena.elements[self.index.try_into_unchecked()]
+			.as_mut()
+			.unwrap_or_else(|| unreachable!())
+	}
+
+	/// Removes the value from the slot, returning it if successful.
+	#[inline]
+	#[must_use]
+	pub fn try_remove(self) -> Option<Value> {
+		let key = self.key();
+
+		self.arena.try_remove(key)
+	}
+}
+
+impl<'a, Key: Referent, Value> VacantSlot<'a, Key, Value> {
+	/// Returns the key the slot will have once filled.
+	#[inline]
+	#[must_use]
+	pub fn key(&self) -> Key {
+		let version = self.arena.elements[self.index.try_into_unchecked()].version();
+
+		Key::new(self.index, version)
+	}
+
+	/// Fills the slot with the value, returning a mutable reference to it.
+	/// Unlinking the slot from the free list takes time proportional to
+	/// the number of vacant slots in front of it. A quarantined slot is
+	/// taken out of the quarantine instead.
+	#[inline]
+	pub fn insert(self, value: Value) -> &'a mut Value {
+		let index = self.index.try_into_unchecked();
+		let elements = &mut self.arena.elements;
+
+		let Element::Vacant { next: after, .. } = elements[index] else {
+			unreachable!()
+		};
+
+		if let Some(position) = self
+			.arena
+			.quarantine
+			.iter()
+			.position(|quarantined| quarantined.try_into_unchecked() == index)
+		{
+			self.arena.quarantine.remove(position);
+		} else if self.arena.next.try_into_unchecked() == index {
+			self.arena.next = after;
+		} else {
+			let mut current = self.arena.next.try_into_unchecked();
+
+			loop {
+				let Element::Vacant { next, .. } = &mut elements[current] else {
+					unreachable!("free list should only contain vacant slots")
+				};
+
+				if next.try_into_unchecked() == index {
+					*next = after;
+
+					break;
+				}
+
+				current = next.try_into_unchecked();
+			}
+		}
+
+		self.arena.len = try_transform(self.arena.len, |len| len.checked_add(1))
+			.unwrap_or_else(|| unreachable!());
+
+		self.arena.epoch = self.arena.epoch.wrapping_add(1);
+		self.arena.grow_max_occupied(self.index);
+
+		let elements = &mut self.arena.elements;
+
+		elements[index].set(value);
+		elements[index].as_mut().unwrap_or_else(|| unreachable!())
+	}
+}
+
+/// A view into the slot addressed by a key, which either holds the value of
+/// the key or will hand out the key once filled.
+///
+/// Created by the [`Arena::entry`] method.
+pub enum Entry<'a, Key: Referent, Value> {
+	/// The slot holds the value of the key.
+	Occupied(OccupiedSlot<'a, Key, Value>),
+	/// The slot is vacant, and filling it produces the key.
+	Vacant(VacantSlot<'a, Key, Value>),
+}
+
+impl<'a, Key: Referent, Value> Entry<'a, Key, Value> {
+	/// Returns the key of the entry.
+	#[inline]
+	#[must_use]
+	pub fn key(&self) -> Key {
+		match self {
+			Self::Occupied(slot) => slot.key(),
+			Self::Vacant(slot) => slot.key(),
+		}
+	}
+
+	/// Calls `f` with the value if the entry is occupied.
+	#[inline]
+	#[must_use]
+	pub fn and_modify(mut self, f: impl FnOnce(&mut Value)) -> Self {
+		if let Self::Occupied(slot) = &mut self {
+			f(slot.get_mut());
+		}
+
+		self
+	}
+
+	/// Returns a mutable reference to the value, inserting `value` if the
+	/// entry is vacant.
+	#[inline]
+	pub fn or_insert(self, value: Value) -> &'a mut Value {
+		self.or_insert_with(|| value)
+	}
+
+	/// Returns a mutable reference to the value, inserting the result of `f`
+	/// if the entry is vacant.
+	#[inline]
+	pub fn or_insert_with(self, f: impl FnOnce() -> Value) -> &'a mut Value {
+		match self {
+			Self::Occupied(slot) => slot.into_mut(),
+			Self::Vacant(slot) => slot.insert(f()),
+		}
+	}
+}
+
+impl<Key: Referent, Value> Arena<Key, Value> {
+	/// Returns a view into the slot addressed by the key, or `None` if the key
+	/// is stale or out of bounds. A vacant slot is only returned if filling it
+	/// would produce the same key, such as one given by [`VacantSlot::key`].
+	#[inline]
+	#[must_use]
+	pub fn entry(&mut self, key: Key) -> Option<Entry<'_, Key, Value>> {
+		self.assert_writable();
+
+		let index = key.index();
+		let element = self.elements.get(index.try_into_unchecked())?;
+
+		if element.version().try_into_unchecked() != key.version().try_into_unchecked() {
+			return None;
+		}
+
+		match element {
+			Element::Occupied { .. } => Some(Entry::Occupied(OccupiedSlot { arena: self, index })),
+			Element::Vacant { .. } => Some(Entry::Vacant(VacantSlot { arena: self, index })),
+			Element::Tombstone { .. } => None,
+		}
+	}
+
+	/// Returns a mutable reference to the value of the key, filling its slot
+	/// with the result of `f` if it is vacant and filling it would produce the
+	/// same key. Returns `None` if the slot holds a different generation.
+	#[inline]
+	pub fn get_or_insert_with(
+		&mut self,
+		key: Key,
+		f: impl FnOnce() -> Value,
+	) -> Option<&mut Value> {
+		self.entry(key).map(|entry| entry.or_insert_with(f))
+	}
+
+	/// Returns a view into the slot at the given raw index, or `None` if it is
+	/// out of bounds or holds a tombstone.
+	#[inline]
+	#[must_use]
+	pub fn slot_entry(&mut self, index: Key::Index) -> Option<SlotEntry<'_, Key, Value>> {
+		self.assert_writable();
+
+		match self.elements.get(index.try_into_unchecked())? {
+			Element::Occupied { .. } => {
+				Some(SlotEntry::Occupied(OccupiedSlot { arena: self, index }))
+			}
+			Element::Vacant { .. } => Some(SlotEntry::Vacant(VacantSlot { arena: self, index })),
+			Element::Tombstone { .. } => None,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		collection::Arena,
+		entry::{Entry, SlotEntry},
+		referent::{Id, Referent},
+	};
+
+	#[test]
+	fn slot_entry() {
+		let mut arena = Arena::<Id, u32>::with_capacity(8);
+
+		let a = arena.insert(10);
+
+		let Some(SlotEntry::Vacant(slot)) = arena.slot_entry(5) else {
+			panic!("slot should be vacant")
+		};
+
+		let b = slot.key();
+
+		*slot.insert(50) += 1;
+
+		assert_eq!(arena[b], 51);
+
+		let Some(SlotEntry::Occupied(mut slot)) = arena.slot_entry(0) else {
+			panic!("slot should be occupied")
+		};
+
+		assert_eq!(slot.key(), a);
+
+		*slot.get_mut() += 1;
+
+		assert_eq!(arena[a], 11);
+		assert!(arena.slot_entry(100).is_none());
+
+		let indices: Vec<_> = (0..7).map(|i| arena.insert(i).index()).collect();
+
+		assert_eq!(indices, [1, 2, 3, 4, 6, 7, 8]);
+		assert_eq!(arena.len(), 9);
+	}
+
+	#[test]
+	fn entry() {
+		let mut arena = Arena::<Id, u32>::with_capacity(8);
+
+		let a = arena.insert(10);
+		let b = arena.insert(20);
+
+		*arena
+			.entry(a)
+			.unwrap()
+			.and_modify(|value| *value += 1)
+			.or_insert(0) += 1;
+
+		assert_eq!(arena[a], 12);
+
+		arena.remove(b);
+
+		assert!(arena.entry(b).is_none());
+		assert!(arena.entry(Id::new(100, b.version())).is_none());
+
+		let c = Id::new(5, b.version());
+
+		assert!(matches!(arena.entry(c), Some(Entry::Vacant(_))));
+		assert_eq!(arena.entry(c).unwrap().key(), c);
+		assert_eq!(*arena.entry(c).unwrap().or_insert_with(|| 50), 50);
+		assert_eq!(arena[c], 50);
+		assert_eq!(arena.len(), 2);
+
+		arena.remove(a);
+
+		assert!(arena.entry(a).is_none());
+	}
+
+	#[test]
+	fn get_or_insert_with() {
+		let mut arena = Arena::<Id, u32>::with_capacity(8);
+
+		let a = arena.insert(10);
+
+		*arena.get_or_insert_with(a, || unreachable!()).unwrap() += 1;
+
+		assert_eq!(arena[a], 11);
+
+		let b = Id::new(3, a.version());
+
+		assert_eq!(arena.get_or_insert_with(b, || 30), Some(&mut 30));
+		assert_eq!(arena[b], 30);
+		assert_eq!(arena.len(), 2);
+
+		arena.remove(a);
+
+		let c = arena.insert(20);
+
+		assert_eq!(arena.get_or_insert_with(a, || 0), None);
+		assert_eq!(arena[c], 20);
+		assert_eq!(arena.len(), 2);
+
+		let indices: Vec<_> = (0..3).map(|i| arena.insert(i).index()).collect();
+
+		assert_eq!(indices, [1, 2, 4]);
+	}
+}
@@ -57,6 +57,11 @@ macro_rules! impl_iterator {
 			fn count(self) -> usize {
 				self.len
 			}
+
+			#[inline]
+			fn last(mut self) -> Option<Self::Item> {
+				self.next_back()
+			}
 		}
 
 		impl<$($lt,)? Key: Referent, Value> DoubleEndedIterator for $name<$($lt,)? Key, Value> {
@@ -123,6 +128,11 @@ macro_rules! impl_wrapper {
 			fn count(self) -> usize {
 				self.iter.count()
 			}
+
+			#[inline]
+			fn last(self) -> Option<Self::Item> {
+				self.iter.last().map($get)
+			}
 		}
 
 		impl<$($lt,)? Key: Referent, Value> DoubleEndedIterator for $name<$($lt,)? Key, Value> {
@@ -180,7 +190,31 @@ impl<Key: Referent, Value> Arena<Key, Value> {
 	#[inline]
 	pub fn iter(&self) -> Iter<'_, Key, Value> {
 		let len = self.len();
-		let iterator = self.elements.iter().enumerate();
+		let iterator = self.elements[..self.occupied_end()].iter().enumerate();
+
+		Iter { iterator, len }
+	}
+
+	/// Returns an iterator over the keys and values of the [`Arena`], starting
+	/// at the slot right after the index of `after`, or at the first slot if `None`.
+	/// The key does not need to be present, which allows resuming iteration
+	/// after its element was removed.
+	#[inline]
+	pub fn iter_from(&self, after: Option<Key>) -> Iter<'_, Key, Value> {
+		let start = after
+			.map_or(0, |key| key.index().try_into_unchecked() + 1)
+			.min(self.capacity());
+
+		let len = self.elements[start..]
+			.iter()
+			.filter(|element| element.as_ref().is_some())
+			.count();
+
+		let mut iterator = self.elements.iter().enumerate();
+
+		if let Some(skip) = start.checked_sub(1) {
+			iterator.nth(skip);
+		}
 
 		Iter { iterator, len }
 	}
@@ -188,12 +222,24 @@ impl<Key: Referent, Value> Arena<Key, Value> {
 	/// Returns a mutable iterator over the keys and values of the [`Arena`].
 	#[inline]
 	pub fn iter_mut(&mut self) -> IterMut<'_, Key, Value> {
+		self.assert_writable();
+
 		let len = self.len();
-		let iterator = self.elements.iter_mut().enumerate();
+		let end = self.occupied_end();
+		let iterator = self.elements[..end].iter_mut().enumerate();
 
 		IterMut { iterator, len }
 	}
 
+	/// Returns a consuming iterator over the keys and values of the [`Arena`],
+	/// along with their position among the occupied slots.
+	#[inline]
+	pub fn into_iter_enumerated(self) -> impl Iterator<Item = (usize, Key, Value)> {
+		self.into_iter()
+			.enumerate()
+			.map(|(position, (key, value))| (position, key, value))
+	}
+
 	/// Returns a consuming iterator over the keys of the [`Arena`].
 	#[inline]
 	pub fn into_keys(self) -> IntoKeys<Key, Value> {
@@ -222,6 +268,16 @@ impl<Key: Referent, Value> Arena<Key, Value> {
 		Values { iter: self.iter() }
 	}
 
+	/// Returns an iterator over the keys of the [`Arena`] and a part of each value,
+	/// as borrowed by `f`, such as the field of a newtype.
+	#[inline]
+	pub fn map_values_ref<'a, B: ?Sized + 'a>(
+		&'a self,
+		f: impl Fn(&Value) -> &B + 'a,
+	) -> impl Iterator<Item = (Key, &'a B)> + 'a {
+		self.iter().map(move |(key, value)| (key, f(value)))
+	}
+
 	/// Returns a mutable iterator over the values of the [`Arena`].
 	#[inline]
 	pub fn values_mut(&mut self) -> ValuesMut<'_, Key, Value> {
@@ -229,6 +285,184 @@ impl<Key: Referent, Value> Arena<Key, Value> {
 			iter: self.iter_mut(),
 		}
 	}
+
+	/// Returns an iterator removing the keys and values of the [`Arena`].
+	///
+	/// The [`Arena`] is left empty once the iterator is dropped, even if it was not
+	/// fully consumed. Values in slots whose version is exhausted can not be removed,
+	/// so they are neither yielded nor taken out.
+	#[inline]
+	pub fn drain(&mut self) -> Drain<'_, Key, Value> {
+		self.assert_writable();
+
+		let back = self.occupied_end();
+		let len = self.elements[..back]
+			.iter()
+			.filter(|element| element.as_ref().is_some() && !element.is_exhausted())
+			.count();
+
+		Drain {
+			arena: self,
+			front: 0,
+			back,
+			len,
+		}
+	}
+
+	/// Returns an iterator removing the keys and values for which `predicate`
+	/// returns `true`, in ascending index order. The predicate may also modify
+	/// the values it keeps.
+	///
+	/// Values the iterator does not reach, because it was dropped early, are
+	/// left untouched. Values in slots whose version is exhausted can not be
+	/// removed, so they are left in place even if they match.
+	#[inline]
+	pub fn extract_if<F>(&mut self, predicate: F) -> ExtractIf<'_, Key, Value, F>
+	where
+		F: FnMut(Key, &mut Value) -> bool,
+	{
+		self.assert_writable();
+
+		ExtractIf {
+			arena: self,
+			index: 0,
+			predicate,
+		}
+	}
+}
+
+/// A draining iterator over the keys and values of the [`Arena`].
+///
+/// Created by the [`Arena::drain`] method.
+pub struct Drain<'a, Key: Referent, Value> {
+	arena: &'a mut Arena<Key, Value>,
+	front: usize,
+	back: usize,
+	len: usize,
+}
+
+impl<Key: Referent, Value> Drain<'_, Key, Value> {
+	#[inline]
+	fn take(&mut self, index: usize) -> Option<(Key, Value)> {
+		let element = &self.arena.elements[index];
+
+		if element.is_exhausted() {
+			return None;
+		}
+
+		let Element::Occupied { version, .. } = *element else {
+			return None;
+		};
+
+		let index = Key::Index::try_from_checked(index).unwrap_or_else(|| unreachable!());
+		let key = Key::new(index, version);
+
+		self.len -= 1;
+		self.arena.try_remove(key).map(|value| (key, value))
+	}
+}
+
+impl<Key: Referent, Value> Iterator for Drain<'_, Key, Value> {
+	type Item = (Key, Value);
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		while self.len != 0 {
+			let index = self.front;
+
+			self.front += 1;
+
+			if let Some(entry) = self.take(index) {
+				return Some(entry);
+			}
+		}
+
+		None
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		(self.len, Some(self.len))
+	}
+}
+
+impl<Key: Referent, Value> DoubleEndedIterator for Drain<'_, Key, Value> {
+	#[inline]
+	fn next_back(&mut self) -> Option<Self::Item> {
+		while self.len != 0 {
+			self.back -= 1;
+
+			if let Some(entry) = self.take(self.back) {
+				return Some(entry);
+			}
+		}
+
+		None
+	}
+}
+
+impl<Key: Referent, Value> ExactSizeIterator for Drain<'_, Key, Value> {}
+
+impl<Key: Referent, Value> FusedIterator for Drain<'_, Key, Value> {}
+
+impl<Key: Referent, Value> Drop for Drain<'_, Key, Value> {
+	fn drop(&mut self) {
+		self.for_each(drop);
+	}
+}
+
+/// An iterator removing the keys and values of the [`Arena`] that match a predicate.
+///
+/// Created by the [`Arena::extract_if`] method.
+pub struct ExtractIf<'a, Key: Referent, Value, F> {
+	arena: &'a mut Arena<Key, Value>,
+	index: usize,
+	predicate: F,
+}
+
+impl<Key: Referent, Value, F> Iterator for ExtractIf<'_, Key, Value, F>
+where
+	F: FnMut(Key, &mut Value) -> bool,
+{
+	type Item = (Key, Value);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		while self.index < self.arena.occupied_end() {
+			let element = &mut self.arena.elements[self.index];
+			let index = Key::Index::try_from_checked(self.index).unwrap_or_else(|| unreachable!());
+
+			self.index += 1;
+
+			if element.is_exhausted() {
+				continue;
+			}
+
+			let version = element.version();
+			let key = Key::new(index, version);
+
+			let Some(value) = element.as_mut() else {
+				continue;
+			};
+
+			if (self.predicate)(key, value) {
+				let value = self.arena.try_remove(key).unwrap_or_else(|| unreachable!());
+
+				return Some((key, value));
+			}
+		}
+
+		None
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		(0, Some(self.arena.len()))
+	}
+}
+
+impl<Key: Referent, Value, F> FusedIterator for ExtractIf<'_, Key, Value, F> where
+	F: FnMut(Key, &mut Value) -> bool
+{
 }
 
 impl<Key: Referent, Value> IntoIterator for Arena<Key, Value> {
@@ -239,7 +473,7 @@ impl<Key: Referent, Value> IntoIterator for Arena<Key, Value> {
 	#[inline]
 	fn into_iter(self) -> Self::IntoIter {
 		let len = self.len();
-		let iterator = self.elements.into_vec().into_iter().enumerate();
+		let iterator = self.elements.into_iter().enumerate();
 
 		IntoIter { iterator, len }
 	}
@@ -428,4 +662,204 @@ mod tests {
 		assert_eq!(iter.next(), Some(5));
 		assert_eq!(iter.next(), None);
 	}
+
+	#[test]
+	fn iterate_values_mut_rev() {
+		let mut arena = Arena::<Id, usize>::new();
+
+		let keys: Vec<_> = (0..8).map(|i| arena.insert(i)).collect();
+
+		arena.remove(keys[1]);
+		arena.remove(keys[4]);
+		arena.remove(keys[7]);
+
+		let mut order = Vec::new();
+
+		for value in arena.values_mut().rev() {
+			order.push(*value);
+
+			*value += 100;
+		}
+
+		assert_eq!(order, [6, 5, 3, 2, 0]);
+		assert!(arena.values().eq(&[100, 102, 103, 105, 106]));
+	}
+
+	#[test]
+	fn iterate_from() {
+		let mut arena = Arena::<Id, usize>::new();
+
+		let keys: Vec<_> = (0..6).map(|i| arena.insert(i)).collect();
+
+		assert!(arena.iter_from(None).map(|entry| entry.0).eq(keys.clone()));
+
+		let mut iter = arena.iter_from(None);
+		let last = iter.nth(1).unwrap().0;
+
+		arena.remove(last);
+		arena.remove(keys[4]);
+
+		let mut iter = arena.iter_from(Some(last));
+
+		assert_eq!(iter.len(), 3);
+		assert_eq!(iter.next(), Some((keys[2], &2)));
+		assert_eq!(iter.next(), Some((keys[3], &3)));
+		assert_eq!(iter.next(), Some((keys[5], &5)));
+		assert_eq!(iter.next(), None);
+
+		assert_eq!(arena.iter_from(Some(keys[5])).next(), None);
+	}
+
+	#[test]
+	fn iterate_last() {
+		let mut arena = Arena::<Id, usize>::with_capacity(64);
+
+		assert_eq!(arena.iter().last(), None);
+
+		let keys: Vec<_> = (0..8).map(|i| arena.insert(i)).collect();
+
+		assert_eq!(arena.iter().last(), Some((keys[7], &7)));
+		assert_eq!(arena.max_occupied, Some(7));
+
+		arena.remove(keys[6]);
+		arena.remove(keys[7]);
+
+		// Removing leaves the bound in place, and reverse iteration skips the
+		// vacant slots below it.
+		assert_eq!(arena.iter().last(), Some((keys[5], &5)));
+		assert_eq!(arena.keys().next_back(), Some(keys[5]));
+		assert_eq!(arena.max_occupied, Some(7));
+
+		let key = arena.insert(6);
+
+		assert_eq!(arena.values_mut().last(), Some(&mut 6));
+		assert_eq!(arena.iter().last(), Some((key, &6)));
+
+		arena.retain(|_, &value| value < 3);
+
+		assert_eq!(arena.values().last(), Some(&2));
+		assert_eq!(arena.max_occupied, Some(2));
+
+		arena.remove(keys[2]);
+		arena.shrink_to_fit();
+
+		assert_eq!(arena.values().last(), Some(&1));
+		assert_eq!(arena.max_occupied, Some(1));
+
+		arena.clear();
+
+		assert_eq!(arena.iter().last(), None);
+		assert_eq!(arena.max_occupied, None);
+	}
+
+	#[test]
+	fn drain() {
+		let mut arena = Arena::<Id, usize>::new();
+
+		let keys: Vec<_> = (0..8).map(|i| arena.insert(i)).collect();
+
+		arena.remove(keys[2]);
+
+		let mut drain = arena.drain();
+
+		assert_eq!(drain.len(), 7);
+		assert_eq!(drain.next(), Some((keys[0], 0)));
+		assert_eq!(drain.next_back(), Some((keys[7], 7)));
+		assert_eq!(drain.next(), Some((keys[1], 1)));
+		assert_eq!(drain.len(), 4);
+
+		drop(drain);
+
+		assert!(arena.is_empty());
+		assert_eq!(arena.iter().next(), None);
+		assert!(keys.iter().all(|&key| arena.get(key).is_none()));
+
+		let fresh: Vec<_> = (0..10).map(|i| arena.insert(i)).collect();
+
+		assert_eq!(arena.len(), 10);
+		assert!(fresh.iter().all(|key| key.index() < 10));
+		assert!(fresh.iter().enumerate().all(|(i, &key)| arena[key] == i));
+		let expected: Vec<_> = arena
+			.iter()
+			.rev()
+			.map(|(key, &value)| (key, value))
+			.collect();
+
+		assert!(arena.drain().rev().eq(expected));
+		assert!(arena.is_empty());
+	}
+
+	#[test]
+	fn extract_if() {
+		let mut arena = Arena::<Id, usize>::new();
+
+		let keys: Vec<_> = (0..10).map(|i| arena.insert(i)).collect();
+
+		{
+			let mut extract = arena.extract_if(|_, value| {
+				*value += 100;
+				*value % 2 == 0
+			});
+
+			assert_eq!(extract.next(), Some((keys[0], 100)));
+			assert_eq!(extract.next(), Some((keys[2], 102)));
+		}
+
+		assert_eq!(arena.len(), 8);
+		assert_eq!(arena.get(keys[0]), None);
+		assert_eq!(arena[keys[1]], 101);
+		assert!((3..10).all(|i| arena[keys[i]] == i));
+
+		let extracted: Vec<_> = arena.extract_if(|_, &mut value| value % 3 == 0).collect();
+
+		assert_eq!(extracted, [(keys[3], 3), (keys[6], 6), (keys[9], 9)]);
+		assert_eq!(arena.len(), 5);
+
+		let fresh: Vec<_> = (0..6).map(|i| arena.insert(i).index()).collect();
+
+		assert_eq!(fresh, [9, 6, 3, 2, 0, 10]);
+		assert!(keys
+			.iter()
+			.all(|&key| arena.get(key).is_none() || [1, 4, 5, 7, 8].contains(&key.index())));
+	}
+
+	#[test]
+	fn map_values_ref() {
+		#[repr(transparent)]
+		struct Meters(f32);
+
+		let mut arena = Arena::<Id, Meters>::new();
+
+		let a = arena.insert(Meters(1.5));
+		let b = arena.insert(Meters(2.0));
+		let c = arena.insert(Meters(4.0));
+
+		arena.remove(b);
+
+		assert!(arena
+			.map_values_ref(|meters| &meters.0)
+			.eq([(a, &1.5), (c, &4.0)]));
+	}
+
+	#[test]
+	fn iterate_enumerated() {
+		let mut arena = Arena::<Id, usize>::new();
+
+		let keys: Vec<_> = (0..6).map(|i| arena.insert(i * 10)).collect();
+
+		arena.remove(keys[0]);
+		arena.remove(keys[3]);
+
+		let entries: Vec<_> = arena.into_iter_enumerated().collect();
+
+		assert_eq!(
+			entries,
+			[
+				(0, keys[1], 10),
+				(1, keys[2], 20),
+				(2, keys[4], 40),
+				(3, keys[5], 50)
+			]
+		);
+	}
 }
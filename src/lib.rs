@@ -27,7 +27,32 @@
 extern crate alloc;
 
 mod element;
+#[cfg(feature = "serde")]
+mod serialize;
 
+pub mod chunked;
+pub mod cohort;
 pub mod collection;
+pub mod diagnostics;
+pub mod entry;
 pub mod iter;
+pub mod occupancy;
+pub mod recycle;
 pub mod referent;
+pub mod region;
+pub mod secondary;
+pub mod sequenced;
+pub mod storage;
+pub mod ttl;
+
+#[cfg(feature = "rayon")]
+pub mod parallel;
+#[cfg(feature = "hashbrown")]
+pub mod sparse;
+#[cfg(feature = "stats")]
+pub mod stats;
+
+#[doc(hidden)]
+pub mod __private {
+	pub use alloc::vec::Vec;
+}
@@ -0,0 +1,121 @@
+use alloc::vec::Vec;
+
+use crate::{collection::Arena, referent::Referent};
+
+const BITS: usize = u64::BITS as usize;
+
+/// A set with one bit per slot of an [`Arena`], set when the slot is occupied.
+///
+/// Created by the [`Arena::occupancy_bitset`] method.
+#[derive(PartialEq, Eq, Hash, Clone, Debug, Default)]
+pub struct Occupancy {
+	words: Vec<u64>,
+	len: usize,
+}
+
+impl Occupancy {
+	/// Returns the number of slots the set covers.
+	#[inline]
+	#[must_use]
+	pub const fn len(&self) -> usize {
+		self.len
+	}
+
+	/// Returns `true` if the set covers no slots.
+	#[inline]
+	#[must_use]
+	pub const fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+
+	/// Returns `true` if the slot at the given index is occupied.
+	#[inline]
+	#[must_use]
+	pub fn is_occupied(&self, index: usize) -> bool {
+		self.words
+			.get(index / BITS)
+			.is_some_and(|word| word & (1 << (index % BITS)) != 0)
+	}
+
+	/// Returns the number of occupied slots.
+	#[inline]
+	#[must_use]
+	pub fn count_occupied(&self) -> usize {
+		self.words
+			.iter()
+			.map(|word| word.count_ones() as usize)
+			.sum()
+	}
+
+	/// Returns the set of slots occupied in both sets.
+	#[must_use]
+	pub fn intersection(&self, other: &Self) -> Self {
+		let words = self
+			.words
+			.iter()
+			.zip(&other.words)
+			.map(|(a, b)| a & b)
+			.collect();
+
+		Self {
+			words,
+			len: self.len.min(other.len),
+		}
+	}
+}
+
+impl<Key: Referent, Value> Arena<Key, Value> {
+	/// Returns a set with the occupancy of every slot of the [`Arena`].
+	#[must_use]
+	pub fn occupancy_bitset(&self) -> Occupancy {
+		let mut words = Vec::new();
+
+		for chunk in self.elements.chunks(BITS) {
+			let word = chunk
+				.iter()
+				.enumerate()
+				.filter(|element| element.1.as_ref().is_some())
+				.fold(0, |word, element| word | (1 << element.0));
+
+			words.push(word);
+		}
+
+		Occupancy {
+			words,
+			len: self.capacity(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{collection::Arena, referent::Id};
+
+	#[test]
+	fn occupancy_bitset() {
+		let mut first = Arena::<Id, usize>::new();
+		let mut second = Arena::<Id, usize>::new();
+
+		let keys: Vec<_> = (0..100).map(|i| first.insert(i)).collect();
+		let _keys: Vec<_> = (0..70).map(|i| second.insert(i)).collect();
+
+		for &key in keys.iter().step_by(3) {
+			first.remove(key);
+		}
+
+		let occupancy = first.occupancy_bitset();
+
+		assert_eq!(occupancy.len(), first.capacity());
+		assert_eq!(occupancy.count_occupied(), first.len());
+
+		for index in 0..occupancy.len() {
+			assert_eq!(occupancy.is_occupied(index), index < 100 && index % 3 != 0);
+		}
+
+		let both = occupancy.intersection(&second.occupancy_bitset());
+
+		for index in 0..both.len() {
+			assert_eq!(both.is_occupied(index), index < 70 && index % 3 != 0);
+		}
+	}
+}
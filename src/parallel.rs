@@ -0,0 +1,191 @@
+use rayon::{
+	iter::{
+		plumbing::UnindexedConsumer, Enumerate, FilterMap, IndexedParallelIterator,
+		IntoParallelIterator, IntoParallelRefIterator, IntoParallelRefMutIterator,
+		ParallelIterator,
+	},
+	slice, vec,
+};
+
+use crate::{
+	collection::Arena,
+	element::Element,
+	referent::{Referent, Similar},
+};
+
+type Slot<Key, Value> = Element<<Key as Referent>::Version, <Key as Referent>::Index, Value>;
+
+type Filtered<Iter, Slot, Item> = FilterMap<Enumerate<Iter>, fn((usize, Slot)) -> Option<Item>>;
+
+macro_rules! impl_parallel_iterator {
+	($name:ident, $item:ty $(, $lifetime:lifetime)?) => {
+		impl<$($lifetime,)? Key, Value> ParallelIterator for $name<$($lifetime,)? Key, Value>
+		where
+			Key: Referent + Send + Sync,
+			Key::Index: Send + Sync,
+			Key::Version: Send + Sync,
+			Value: Send + Sync,
+		{
+			type Item = $item;
+
+			#[inline]
+			fn drive_unindexed<C: UnindexedConsumer<Self::Item>>(self, consumer: C) -> C::Result {
+				self.inner.drive_unindexed(consumer)
+			}
+		}
+	};
+}
+
+/// A parallel iterator over the keys and values of the [`Arena`].
+///
+/// Created by the [`IntoParallelIterator`] implementation of `&Arena`.
+#[allow(clippy::type_complexity)]
+pub struct ParIter<'a, Key: Referent, Value> {
+	inner: Filtered<slice::Iter<'a, Slot<Key, Value>>, &'a Slot<Key, Value>, (Key, &'a Value)>,
+}
+
+/// A parallel mutable iterator over the keys and values of the [`Arena`].
+///
+/// Created by the [`IntoParallelIterator`] implementation of `&mut Arena`.
+#[allow(clippy::type_complexity)]
+pub struct ParIterMut<'a, Key: Referent, Value> {
+	inner: Filtered<
+		slice::IterMut<'a, Slot<Key, Value>>,
+		&'a mut Slot<Key, Value>,
+		(Key, &'a mut Value),
+	>,
+}
+
+/// A parallel owning iterator over the keys and values of the [`Arena`].
+///
+/// Created by the [`IntoParallelIterator`] implementation of `Arena`.
+#[allow(clippy::type_complexity)]
+pub struct IntoParIter<Key: Referent, Value> {
+	inner: Filtered<vec::IntoIter<Slot<Key, Value>>, Slot<Key, Value>, (Key, Value)>,
+}
+
+impl_parallel_iterator!(ParIter, (Key, &'a Value), 'a);
+impl_parallel_iterator!(ParIterMut, (Key, &'a mut Value), 'a);
+impl_parallel_iterator!(IntoParIter, (Key, Value));
+
+fn key_of<Key: Referent>(index: usize, version: Key::Version) -> Key {
+	let index = Key::Index::try_from_checked(index).unwrap_or_else(|| unreachable!());
+
+	Key::new(index, version)
+}
+
+impl<'a, Key, Value> IntoParallelIterator for &'a Arena<Key, Value>
+where
+	Key: Referent + Send + Sync,
+	Key::Index: Send + Sync,
+	Key::Version: Send + Sync,
+	Value: Send + Sync,
+{
+	type Iter = ParIter<'a, Key, Value>;
+	type Item = (Key, &'a Value);
+
+	#[inline]
+	fn into_par_iter(self) -> Self::Iter {
+		let inner = self.elements.par_iter().enumerate().filter_map(
+			(|(index, element): (usize, &Slot<Key, Value>)| {
+				let value = element.as_ref()?;
+
+				Some((key_of(index, element.version()), value))
+			}) as fn(_) -> _,
+		);
+
+		ParIter { inner }
+	}
+}
+
+impl<'a, Key, Value> IntoParallelIterator for &'a mut Arena<Key, Value>
+where
+	Key: Referent + Send + Sync,
+	Key::Index: Send + Sync,
+	Key::Version: Send + Sync,
+	Value: Send + Sync,
+{
+	type Iter = ParIterMut<'a, Key, Value>;
+	type Item = (Key, &'a mut Value);
+
+	#[inline]
+	fn into_par_iter(self) -> Self::Iter {
+		self.assert_writable();
+
+		let inner = self.elements.par_iter_mut().enumerate().filter_map(
+			(|(index, element): (usize, &mut Slot<Key, Value>)| {
+				let version = element.version();
+				let value = element.as_mut()?;
+
+				Some((key_of(index, version), value))
+			}) as fn(_) -> _,
+		);
+
+		ParIterMut { inner }
+	}
+}
+
+impl<Key, Value> IntoParallelIterator for Arena<Key, Value>
+where
+	Key: Referent + Send + Sync,
+	Key::Index: Send + Sync,
+	Key::Version: Send + Sync,
+	Value: Send + Sync,
+{
+	type Iter = IntoParIter<Key, Value>;
+	type Item = (Key, Value);
+
+	#[inline]
+	fn into_par_iter(self) -> Self::Iter {
+		let inner = self.elements.into_par_iter().enumerate().filter_map(
+			(|(index, element): (usize, Slot<Key, Value>)| {
+				let version = element.version();
+				let value = element.into_inner()?;
+
+				Some((key_of(index, version), value))
+			}) as fn(_) -> _,
+		);
+
+		IntoParIter { inner }
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use rayon::iter::{IntoParallelIterator, IntoParallelRefIterator, ParallelIterator};
+
+	use crate::{
+		collection::Arena,
+		referent::{Id, Referent},
+	};
+
+	#[test]
+	fn parallel_sum() {
+		let mut arena = Arena::<Id, u64>::new();
+
+		let keys: Vec<_> = (0..10_000).map(|i| arena.insert(i)).collect();
+
+		for &key in keys.iter().step_by(3) {
+			arena.remove(key);
+		}
+
+		let sequential: u64 = arena.values().sum();
+		let parallel: u64 = arena.par_iter().map(|(_, value)| value).sum();
+
+		assert_eq!(parallel, sequential);
+
+		(&mut arena)
+			.into_par_iter()
+			.for_each(|(key, value)| *value += u64::from(key.index()));
+
+		let expected: Vec<_> = arena.iter().map(|(key, &value)| (key, value)).collect();
+		let mut borrowed: Vec<_> = arena.par_iter().map(|(key, &value)| (key, value)).collect();
+		let mut owned: Vec<_> = arena.into_par_iter().collect();
+
+		borrowed.sort_unstable();
+		owned.sort_unstable();
+
+		assert_eq!(borrowed, expected);
+		assert_eq!(owned, expected);
+	}
+}
@@ -0,0 +1,172 @@
+use alloc::vec::Vec;
+
+use crate::{collection::Arena, referent::Referent};
+
+/// A [`RecyclingArena`] is an [`Arena`] that keeps the values it removes in a
+/// small pool, so that inserting can reuse them and the memory they own
+/// instead of building new ones. This suits values such as `Vec` or `Box`
+/// that are removed and inserted often.
+#[derive(Clone)]
+pub struct RecyclingArena<Key: Referent, Value> {
+	arena: Arena<Key, Value>,
+	pool: Vec<Value>,
+	pool_limit: usize,
+}
+
+impl<Key: Referent, Value> Default for RecyclingArena<Key, Value> {
+	#[inline]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<Key: Referent, Value> RecyclingArena<Key, Value> {
+	/// Creates a new, empty [`RecyclingArena`] pooling up to 16 values.
+	#[inline]
+	#[must_use]
+	pub const fn new() -> Self {
+		Self::with_pool_limit(16)
+	}
+
+	/// Creates a new, empty [`RecyclingArena`] pooling up to `pool_limit` values.
+	/// Values removed while the pool is full are dropped.
+	#[inline]
+	#[must_use]
+	pub const fn with_pool_limit(pool_limit: usize) -> Self {
+		Self {
+			arena: Arena::new(),
+			pool: Vec::new(),
+			pool_limit,
+		}
+	}
+
+	/// Returns the number of elements in the [`RecyclingArena`].
+	#[inline]
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.arena.len()
+	}
+
+	/// Returns `true` if the [`RecyclingArena`] contains no elements.
+	#[inline]
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.arena.is_empty()
+	}
+
+	/// Returns the number of removed values waiting in the pool.
+	#[inline]
+	#[must_use]
+	pub fn pooled(&self) -> usize {
+		self.pool.len()
+	}
+
+	/// Inserts a value into the [`RecyclingArena`], returning the key.
+	///
+	/// # Panics
+	///
+	/// Panics if the [`RecyclingArena`] is at capacity.
+	#[inline]
+	#[must_use]
+	pub fn insert(&mut self, value: Value) -> Key {
+		self.arena.insert(value)
+	}
+
+	/// Returns a reference to the value corresponding to the given key.
+	#[inline]
+	#[must_use]
+	pub fn get(&self, key: Key) -> Option<&Value> {
+		self.arena.get(key)
+	}
+
+	/// Returns a mutable reference to the value corresponding to the given key.
+	#[inline]
+	#[must_use]
+	pub fn get_mut(&mut self, key: Key) -> Option<&mut Value> {
+		self.arena.get_mut(key)
+	}
+
+	/// Removes a key from the [`RecyclingArena`], keeping its value in the pool
+	/// if there is room. Returns `true` if the key was present.
+	#[inline]
+	pub fn remove(&mut self, key: Key) -> bool {
+		let Some(value) = self.arena.try_remove(key) else {
+			return false;
+		};
+
+		if self.pool.len() < self.pool_limit {
+			self.pool.push(value);
+		}
+
+		true
+	}
+
+	/// Attempts to remove a key from the [`RecyclingArena`], returning the value
+	/// instead of pooling it.
+	#[inline]
+	#[must_use]
+	pub fn try_remove(&mut self, key: Key) -> Option<Value> {
+		self.arena.try_remove(key)
+	}
+
+	/// Drops every value in the pool.
+	#[inline]
+	pub fn clear_pool(&mut self) {
+		self.pool.clear();
+	}
+}
+
+impl<Key: Referent, Value: Default> RecyclingArena<Key, Value> {
+	/// Inserts the most recently pooled value, or a default one if the pool is
+	/// empty, after passing it to `init` to set it up. Returns the key.
+	///
+	/// # Panics
+	///
+	/// Panics if the [`RecyclingArena`] is at capacity.
+	#[must_use]
+	pub fn insert_or_recycle(&mut self, init: impl FnOnce(&mut Value)) -> Key {
+		let mut value = self.pool.pop().unwrap_or_default();
+
+		init(&mut value);
+
+		self.arena.insert(value)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{recycle::RecyclingArena, referent::Id};
+
+	#[test]
+	fn reuse_allocation() {
+		let mut arena = RecyclingArena::<Id, Vec<u8>>::with_pool_limit(1);
+
+		let a = arena.insert_or_recycle(|buffer| buffer.extend_from_slice(b"hello"));
+		let pointer = arena.get(a).unwrap().as_ptr();
+
+		assert!(arena.remove(a));
+		assert!(!arena.remove(a));
+		assert_eq!(arena.pooled(), 1);
+
+		let b = arena.insert_or_recycle(|buffer| {
+			buffer.clear();
+			buffer.extend_from_slice(b"bye");
+		});
+
+		assert_eq!(arena.pooled(), 0);
+		assert_eq!(arena.get(b).unwrap(), b"bye");
+		assert_eq!(arena.get(b).unwrap().as_ptr(), pointer);
+
+		let c = arena.insert(vec![1]);
+
+		arena.remove(b);
+		arena.remove(c);
+
+		assert_eq!(arena.pooled(), 1);
+		assert!(arena.is_empty());
+
+		let d = arena.insert_or_recycle(|_| {});
+
+		assert_eq!(arena.get(d).unwrap(), b"bye");
+	}
+}
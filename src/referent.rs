@@ -59,6 +59,11 @@ where
 
 /// A referent is a key that can be used to access an element in an arena.
 /// It may be manually implemented for more control, or you can use the [`Id`] type.
+///
+/// Arenas only read a key through [`Referent::index`] and [`Referent::version`],
+/// so a key may carry extra fields, such as a tag for the kind of value it refers
+/// to. Those fields are ignored for addressing. Keys handed out by an arena are
+/// built with [`Referent::new`], so they hold whatever it puts in the extra fields.
 pub trait Referent: Copy {
 	type Index: Similar<usize> + Copy;
 	type Version: Similar<NonZeroU64> + Copy;
@@ -194,3 +199,254 @@ impl<Index: Similar<usize>, Version, T> ops::IndexMut<Id<Index, Version>> for al
 		ops::IndexMut::index_mut(self, key.index.try_into_unchecked())
 	}
 }
+
+/// An index limited to values up to `MAX`. Since arenas keep the maximum index
+/// free to mark the end of their free list, at most `MAX` slots can be used.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
+pub struct BoundedIndex<const MAX: u32>(u32);
+
+impl<const MAX: u32> Similar<usize> for BoundedIndex<MAX> {
+	const MIN: Self = Self(0);
+	const MAX: Self = Self(MAX);
+
+	#[inline]
+	fn try_from_checked(value: usize) -> Option<Self> {
+		u32::try_from(value)
+			.ok()
+			.filter(|&value| value <= MAX)
+			.map(Self)
+	}
+
+	#[inline]
+	fn try_into_unchecked(self) -> usize {
+		self.0.try_into().expect("value must be representable")
+	}
+}
+
+/// A key whose index is below `MAX`, so that an arena using it can never hold
+/// more than `MAX` values.
+///
+/// ```rust
+/// # use arena::{collection::Arena, referent::BoundedId};
+/// let mut arena = Arena::<BoundedId<2>, &str>::new();
+///
+/// let _alice = arena.insert("Alice");
+/// let _bob = arena.insert("Bob");
+///
+/// assert!(arena.is_full());
+/// assert_eq!(arena.try_insert("Carol"), None);
+/// ```
+pub type BoundedId<const MAX: u32, Version = defaults::Version> = Id<BoundedIndex<MAX>, Version>;
+
+/// The index of a [`PackedId`], limited to the low `BITS` bits of a `u32`.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
+pub struct PackedIndex<const BITS: u32>(u32);
+
+impl<const BITS: u32> Similar<usize> for PackedIndex<BITS> {
+	const MIN: Self = Self(0);
+	const MAX: Self = Self(u32::MAX >> (32 - BITS));
+
+	#[inline]
+	fn try_from_checked(value: usize) -> Option<Self> {
+		u32::try_from(value)
+			.ok()
+			.filter(|&value| value <= Self::MAX.0)
+			.map(Self)
+	}
+
+	#[inline]
+	fn try_into_unchecked(self) -> usize {
+		self.0.try_into().expect("value must be representable")
+	}
+}
+
+/// The version of a [`PackedId`], limited to the high `32 - BITS` bits of a `u32`.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
+pub struct PackedVersion<const BITS: u32>(NonZeroU32);
+
+impl<const BITS: u32> Similar<NonZeroU64> for PackedVersion<BITS> {
+	const MIN: Self = Self(NonZeroU32::MIN);
+	const MAX: Self = match NonZeroU32::new(u32::MAX >> BITS) {
+		Some(max) => Self(max),
+		None => panic!("`PackedVersion` needs at least one bit"),
+	};
+
+	#[inline]
+	fn try_from_checked(value: NonZeroU64) -> Option<Self> {
+		NonZeroU32::try_from(value)
+			.ok()
+			.filter(|&value| value <= Self::MAX.0)
+			.map(Self)
+	}
+
+	#[inline]
+	fn try_into_unchecked(self) -> NonZeroU64 {
+		self.0.into()
+	}
+}
+
+/// A key packing both its index and version into a single `u32`, with the
+/// index in the low `IDX_BITS` bits and the version in the rest. `IDX_BITS`
+/// must be between 1 and 31, and an `Option<PackedId>` takes no extra space.
+///
+/// ```rust
+/// # use arena::{collection::Arena, referent::PackedId};
+/// let mut arena = Arena::<PackedId<24>, &str>::new();
+///
+/// let key = arena.insert("Alice");
+///
+/// assert_eq!(arena[key], "Alice");
+/// assert_eq!(size_of::<Option<PackedId<24>>>(), 4);
+/// ```
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
+pub struct PackedId<const IDX_BITS: u32>(NonZeroU32);
+
+impl<const IDX_BITS: u32> Referent for PackedId<IDX_BITS> {
+	type Index = PackedIndex<IDX_BITS>;
+	type Version = PackedVersion<IDX_BITS>;
+
+	#[inline]
+	fn new(index: Self::Index, version: Self::Version) -> Self {
+		let packed = NonZeroU32::new(version.0.get() << IDX_BITS | index.0)
+			.unwrap_or_else(|| unreachable!());
+
+		Self(packed)
+	}
+
+	#[inline]
+	fn index(self) -> Self::Index {
+		PackedIndex(self.0.get() & PackedIndex::<IDX_BITS>::MAX.0)
+	}
+
+	#[inline]
+	fn version(self) -> Self::Version {
+		let version = NonZeroU32::new(self.0.get() >> IDX_BITS).unwrap_or_else(|| unreachable!());
+
+		PackedVersion(version)
+	}
+}
+
+impl<const IDX_BITS: u32> core::fmt::Display for PackedId<IDX_BITS> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		write!(f, "I{}", self.index().0)
+	}
+}
+
+/// Defines a newtype key over [`Id`], so that keys of different arenas can not
+/// be mixed up. The generated type implements [`Referent`] and can index slices
+/// and `Vec`s like [`Id`]. It also converts to and from [`Id`], for interop with
+/// code that is not generic over the key type.
+///
+/// ```rust
+/// # use arena::{collection::Arena, define_key, referent::Id};
+/// define_key!(pub PlayerId);
+/// define_key!(pub MonsterId);
+///
+/// let mut players = Arena::<PlayerId, &str>::new();
+/// let mut monsters = Arena::<MonsterId, &str>::new();
+///
+/// let player = players.insert("Alice");
+/// let monster = monsters.insert("Goblin");
+///
+/// assert_eq!(players[player], "Alice");
+/// assert_eq!(monsters[monster], "Goblin");
+///
+/// let mut scores = vec![0; players.capacity()];
+///
+/// scores[player] += 10;
+///
+/// assert_eq!(scores[player], 10);
+///
+/// let id = Id::from(player);
+///
+/// assert_eq!(PlayerId::from(id), player);
+/// assert_eq!(players.remove(player), "Alice");
+/// assert_eq!(players.get(player), None);
+/// ```
+///
+/// ```compile_fail
+/// # use arena::{collection::Arena, define_key};
+/// define_key!(PlayerId);
+/// define_key!(MonsterId);
+///
+/// let mut players = Arena::<PlayerId, &str>::new();
+/// let monsters = Arena::<MonsterId, &str>::new();
+///
+/// let player = players.insert("Alice");
+/// let _ = monsters.get(player);
+/// ```
+#[macro_export]
+macro_rules! define_key {
+	($(#[$attr:meta])* $vis:vis $name:ident) => {
+		$(#[$attr])*
+		#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug, Default)]
+		#[repr(transparent)]
+		$vis struct $name($crate::referent::Id);
+
+		impl $crate::referent::Referent for $name {
+			type Index = <$crate::referent::Id as $crate::referent::Referent>::Index;
+			type Version = <$crate::referent::Id as $crate::referent::Referent>::Version;
+
+			#[inline]
+			fn new(index: Self::Index, version: Self::Version) -> Self {
+				Self($crate::referent::Referent::new(index, version))
+			}
+
+			#[inline]
+			fn index(self) -> Self::Index {
+				$crate::referent::Referent::index(self.0)
+			}
+
+			#[inline]
+			fn version(self) -> Self::Version {
+				$crate::referent::Referent::version(self.0)
+			}
+		}
+
+		impl ::core::convert::From<$crate::referent::Id> for $name {
+			#[inline]
+			fn from(id: $crate::referent::Id) -> Self {
+				Self(id)
+			}
+		}
+
+		impl ::core::convert::From<$name> for $crate::referent::Id {
+			#[inline]
+			fn from(key: $name) -> Self {
+				key.0
+			}
+		}
+
+		impl<T> ::core::ops::Index<$name> for [T] {
+			type Output = T;
+
+			#[inline]
+			fn index(&self, key: $name) -> &Self::Output {
+				&self[key.0]
+			}
+		}
+
+		impl<T> ::core::ops::IndexMut<$name> for [T] {
+			#[inline]
+			fn index_mut(&mut self, key: $name) -> &mut Self::Output {
+				&mut self[key.0]
+			}
+		}
+
+		impl<T> ::core::ops::Index<$name> for $crate::__private::Vec<T> {
+			type Output = T;
+
+			#[inline]
+			fn index(&self, key: $name) -> &Self::Output {
+				&self[key.0]
+			}
+		}
+
+		impl<T> ::core::ops::IndexMut<$name> for $crate::__private::Vec<T> {
+			#[inline]
+			fn index_mut(&mut self, key: $name) -> &mut Self::Output {
+				&mut self[key.0]
+			}
+		}
+	};
+}
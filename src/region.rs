@@ -0,0 +1,210 @@
+use alloc::{vec, vec::Vec};
+
+use crate::{
+	element::{Element, List},
+	referent::{Referent, Similar},
+};
+
+/// A [`RegionArena`] is like an [`Arena`](crate::collection::Arena), but keeps a
+/// separate free list for each of a fixed number of regions. Every slot belongs
+/// to the region it was first allocated for, and is only reused by insertions
+/// into that same region, keeping related values near each other.
+#[derive(Clone)]
+pub struct RegionArena<Key: Referent, Value> {
+	elements: List<Key::Version, Key::Index, Value>,
+	regions: Vec<usize>,
+	heads: Vec<Key::Index>,
+	len: usize,
+}
+
+impl<Key: Referent, Value> RegionArena<Key, Value> {
+	/// Creates a new, empty [`RegionArena`] with `regions` regions. The end of
+	/// each free list is marked by `Key::Index::MAX`, which is never handed out.
+	#[inline]
+	#[must_use]
+	pub fn new(regions: usize) -> Self {
+		Self {
+			elements: List::new(),
+			regions: Vec::new(),
+			heads: vec![Key::Index::MAX; regions],
+			len: 0,
+		}
+	}
+
+	/// Returns the number of regions of the [`RegionArena`].
+	#[inline]
+	#[must_use]
+	pub fn region_count(&self) -> usize {
+		self.heads.len()
+	}
+
+	/// Returns the number of elements in the [`RegionArena`].
+	#[inline]
+	#[must_use]
+	pub const fn len(&self) -> usize {
+		self.len
+	}
+
+	/// Returns `true` if the [`RegionArena`] contains no elements.
+	#[inline]
+	#[must_use]
+	pub const fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+
+	/// Returns the region the slot of the given key belongs to, if it exists.
+	#[inline]
+	#[must_use]
+	pub fn region_of(&self, key: Key) -> Option<usize> {
+		self.regions.get(key.index().try_into_unchecked()).copied()
+	}
+
+	/// Returns a reference to the value corresponding to the given key.
+	#[inline]
+	#[must_use]
+	pub fn get(&self, key: Key) -> Option<&Value> {
+		self.elements
+			.get(key.index().try_into_unchecked())
+			.and_then(|element| element.get(key.version()))
+	}
+
+	/// Returns a mutable reference to the value corresponding to the given key.
+	#[inline]
+	#[must_use]
+	pub fn get_mut(&mut self, key: Key) -> Option<&mut Value> {
+		self.elements
+			.get_mut(key.index().try_into_unchecked())
+			.and_then(|element| element.get_mut(key.version()))
+	}
+
+	/// Attempts to insert a value using a free slot of the given region, or a new
+	/// slot if the region has none, returning the key if successful.
+	///
+	/// # Panics
+	///
+	/// Panics if the region does not exist.
+	#[must_use]
+	pub fn try_insert_in_region(&mut self, region: usize, value: Value) -> Option<Key> {
+		let head = self.heads[region];
+		let index = if head.try_into_unchecked() == Key::Index::MAX.try_into_unchecked() {
+			let index = Key::Index::try_from_checked(self.elements.len()).filter(|index| {
+				index.try_into_unchecked() != Key::Index::MAX.try_into_unchecked()
+			})?;
+
+			self.elements.push(Element::Vacant {
+				version: Key::Version::MIN,
+				next: Key::Index::MAX,
+			});
+
+			self.regions.push(region);
+
+			index
+		} else {
+			head
+		};
+
+		let (version, next) = self.elements[index.try_into_unchecked()].set(value);
+
+		if index.try_into_unchecked() == head.try_into_unchecked() {
+			self.heads[region] = next;
+		}
+
+		self.len += 1;
+
+		Some(Key::new(index, version))
+	}
+
+	/// Inserts a value using a free slot of the given region, or a new slot if the
+	/// region has none, returning the key.
+	///
+	/// # Panics
+	///
+	/// Panics if the region does not exist or the [`RegionArena`] is at capacity.
+	#[inline]
+	#[must_use]
+	pub fn insert_in_region(&mut self, region: usize, value: Value) -> Key {
+		self.try_insert_in_region(region, value)
+			.expect("should be able to insert")
+	}
+
+	/// Attempts to remove a key from the [`RegionArena`], returning the value if
+	/// successful. The slot is returned to the free list of its region.
+	#[must_use]
+	pub fn try_remove(&mut self, key: Key) -> Option<Value> {
+		let index = key.index().try_into_unchecked();
+		let region = *self.regions.get(index)?;
+		let value = self
+			.elements
+			.get_mut(index)
+			.filter(|element| element.get(key.version()).is_some())
+			.and_then(|element| element.reset(self.heads[region]))?;
+
+		self.heads[region] = key.index();
+		self.len -= 1;
+
+		Some(value)
+	}
+
+	/// Removes a key from the [`RegionArena`], returning the value.
+	///
+	/// # Panics
+	///
+	/// Panics if the key is not present in the [`RegionArena`].
+	#[inline]
+	pub fn remove(&mut self, key: Key) -> Value {
+		self.try_remove(key).expect("should be able to remove")
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		referent::{Id, Referent},
+		region::RegionArena,
+	};
+
+	#[test]
+	fn reuse_within_region() {
+		let mut arena = RegionArena::<Id, u32>::new(2);
+
+		let a = arena.insert_in_region(0, 10);
+		let b = arena.insert_in_region(1, 20);
+		let c = arena.insert_in_region(0, 30);
+
+		assert_eq!(arena.region_of(b), Some(1));
+
+		arena.remove(a);
+
+		let d = arena.insert_in_region(1, 40);
+
+		assert_eq!(d.index(), 3);
+		assert_eq!(arena.region_of(d), Some(1));
+
+		let e = arena.insert_in_region(0, 50);
+
+		assert_eq!(e.index(), a.index());
+		assert_eq!(arena.get(a), None);
+		assert_eq!(arena.get(e), Some(&50));
+
+		arena.remove(b);
+		arena.remove(d);
+
+		let f = arena.insert_in_region(1, 60);
+		let g = arena.insert_in_region(1, 70);
+
+		assert_eq!([f.index(), g.index()], [d.index(), b.index()]);
+		assert_eq!(arena.get(c), Some(&30));
+		assert_eq!(arena.len(), 4);
+	}
+
+	#[test]
+	fn region_capacity() {
+		let mut arena = RegionArena::<Id<u8>, u8>::new(3);
+
+		for i in 0..255 {
+			let _key = arena.insert_in_region(usize::from(i % 3), i);
+		}
+
+		assert_eq!(arena.try_insert_in_region(0, 0), None);
+	}
+}
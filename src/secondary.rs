@@ -0,0 +1,322 @@
+use alloc::vec::Vec;
+
+use crate::referent::{Referent, Similar};
+
+/// A [`SecondaryMap`] associates extra data with the keys of an
+/// [`Arena`](crate::collection::Arena), without storing it in the arena itself.
+/// It is backed by a `Vec` indexed like the arena, so it suits data attached
+/// to most keys.
+///
+/// Entries are tied to the version of the key they were inserted with, so
+/// keys of removed elements do not resolve to data of newer ones.
+pub struct SecondaryMap<Key: Referent, Value> {
+	slots: Vec<Option<(Key::Version, Value)>>,
+	len: usize,
+}
+
+impl<Key: Referent, Value> Default for SecondaryMap<Key, Value> {
+	#[inline]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<Key: Referent, Value> SecondaryMap<Key, Value> {
+	/// Creates a new, empty [`SecondaryMap`].
+	#[inline]
+	#[must_use]
+	pub const fn new() -> Self {
+		Self {
+			slots: Vec::new(),
+			len: 0,
+		}
+	}
+
+	/// Returns the number of elements in the [`SecondaryMap`].
+	#[inline]
+	#[must_use]
+	pub const fn len(&self) -> usize {
+		self.len
+	}
+
+	/// Returns `true` if the [`SecondaryMap`] contains no elements.
+	#[inline]
+	#[must_use]
+	pub const fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+
+	/// Returns the number of slots of the [`SecondaryMap`], which is one past the
+	/// highest index it has held a value for.
+	#[inline]
+	#[must_use]
+	pub fn capacity(&self) -> usize {
+		self.slots.len()
+	}
+
+	/// Inserts a value for the given key, returning the previous value of the
+	/// same key. Keys older than the one already present are ignored.
+	pub fn insert(&mut self, key: Key, value: Value) -> Option<Value> {
+		let index = key.index().try_into_unchecked();
+
+		if index >= self.slots.len() {
+			self.slots.resize_with(index + 1, || None);
+		}
+
+		let version = key.version();
+		let slot = &mut self.slots[index];
+
+		if let Some((current, _)) = slot {
+			let current = current.try_into_unchecked();
+			let parameter = version.try_into_unchecked();
+
+			if parameter < current {
+				return None;
+			}
+
+			let (_, last) = slot.replace((version, value))?;
+
+			(parameter == current).then_some(last)
+		} else {
+			*slot = Some((version, value));
+			self.len += 1;
+
+			None
+		}
+	}
+
+	/// Returns a reference to the value corresponding to the given key.
+	#[inline]
+	#[must_use]
+	pub fn get(&self, key: Key) -> Option<&Value> {
+		let (version, value) = self.slots.get(key.index().try_into_unchecked())?.as_ref()?;
+
+		(version.try_into_unchecked() == key.version().try_into_unchecked()).then_some(value)
+	}
+
+	/// Returns a mutable reference to the value corresponding to the given key.
+	#[inline]
+	#[must_use]
+	pub fn get_mut(&mut self, key: Key) -> Option<&mut Value> {
+		let (version, value) = self
+			.slots
+			.get_mut(key.index().try_into_unchecked())?
+			.as_mut()?;
+
+		(version.try_into_unchecked() == key.version().try_into_unchecked()).then_some(value)
+	}
+
+	/// Returns `true` if the [`SecondaryMap`] contains a value for the given key.
+	#[inline]
+	#[must_use]
+	pub fn contains_key(&self, key: Key) -> bool {
+		self.get(key).is_some()
+	}
+
+	/// Removes a key from the [`SecondaryMap`], returning the value if present.
+	#[inline]
+	pub fn remove(&mut self, key: Key) -> Option<Value> {
+		self.get(key)?;
+		self.len -= 1;
+
+		self.slots[key.index().try_into_unchecked()]
+			.take()
+			.map(|entry| entry.1)
+	}
+
+	/// Removes all values from the [`SecondaryMap`], keeping its slots.
+	#[inline]
+	pub fn clear(&mut self) {
+		self.slots.fill_with(|| None);
+		self.len = 0;
+	}
+
+	/// Truncates the trailing empty slots of the [`SecondaryMap`], keeping at
+	/// least `min_capacity` slots, and releases the memory they used.
+	///
+	/// After shrinking an [`Arena`](crate::collection::Arena), pass its capacity
+	/// so that the map tracks it. The arena only truncates vacant slots, so the
+	/// map only loses the slots of values that were removed, as long as their
+	/// entries were removed along with them.
+	///
+	/// ```rust
+	/// # use arena::{collection::Arena, referent::Id, secondary::SecondaryMap};
+	/// let mut arena = Arena::<Id, &str>::new();
+	/// let mut names = SecondaryMap::<Id, &str>::new();
+	///
+	/// let keys: Vec<_> = (0..8).map(|_| arena.insert("node")).collect();
+	///
+	/// for &key in &keys {
+	///     names.insert(key, "name");
+	/// }
+	///
+	/// for &key in &keys[2..] {
+	///     arena.remove(key);
+	///     names.remove(key);
+	/// }
+	///
+	/// arena.shrink_to_fit();
+	/// names.shrink_to(arena.capacity());
+	///
+	/// assert_eq!(names.capacity(), arena.capacity());
+	/// assert_eq!(names.get(keys[1]), Some(&"name"));
+	/// ```
+	pub fn shrink_to(&mut self, min_capacity: usize) {
+		let end = self
+			.slots
+			.iter()
+			.rposition(Option::is_some)
+			.map_or(0, |index| index + 1)
+			.max(min_capacity);
+
+		self.slots.truncate(end);
+		self.slots.shrink_to_fit();
+	}
+
+	/// Truncates the trailing empty slots of the [`SecondaryMap`] and releases
+	/// the memory they used.
+	#[inline]
+	pub fn shrink_to_fit(&mut self) {
+		self.shrink_to(0);
+	}
+
+	/// Returns an iterator over the keys and values of the [`SecondaryMap`],
+	/// in ascending index order.
+	#[inline]
+	pub fn iter(&self) -> impl Iterator<Item = (Key, &Value)> {
+		self.slots.iter().enumerate().filter_map(|(index, slot)| {
+			let (version, value) = slot.as_ref()?;
+			let index = Key::Index::try_from_checked(index)?;
+
+			Some((Key::new(index, *version), value))
+		})
+	}
+
+	/// Returns a mutable iterator over the keys and values of the [`SecondaryMap`],
+	/// in ascending index order.
+	#[inline]
+	pub fn iter_mut(&mut self) -> impl Iterator<Item = (Key, &mut Value)> {
+		self.slots
+			.iter_mut()
+			.enumerate()
+			.filter_map(|(index, slot)| {
+				let (version, value) = slot.as_mut()?;
+				let index = Key::Index::try_from_checked(index)?;
+
+				Some((Key::new(index, *version), value))
+			})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		collection::Arena,
+		referent::{Id, Referent},
+		secondary::SecondaryMap,
+	};
+
+	#[test]
+	fn versioned_entries() {
+		let mut arena = Arena::<Id, u32>::new();
+		let mut map = SecondaryMap::<Id, &str>::new();
+
+		let a = arena.insert(10);
+		let c = arena.insert(30);
+
+		assert_eq!(map.insert(a, "a"), None);
+		assert_eq!(map.insert(a, "A"), Some("a"));
+		assert_eq!(map.insert(c, "c"), None);
+		assert_eq!(map.get(a), Some(&"A"));
+
+		arena.remove(a);
+
+		let b = arena.insert(20);
+
+		assert_eq!(map.get(b), None);
+		assert_eq!(map.insert(b, "b"), None);
+		assert_eq!(map.insert(a, "stale"), None);
+		assert_eq!(map.get(a), None);
+		assert_eq!(map.remove(a), None);
+
+		*map.get_mut(c).unwrap() = "C";
+
+		assert!(map.iter().eq([(b, &"b"), (c, &"C")]));
+		assert_eq!(map.remove(b), Some("b"));
+		assert_eq!(map.len(), 1);
+	}
+
+	#[test]
+	fn shrink_to() {
+		let mut arena = Arena::<Id, u32>::new();
+		let mut map = SecondaryMap::<Id, u32>::new();
+
+		let keys: Vec<_> = (0..10).map(|i| arena.insert(i)).collect();
+
+		for &key in &keys {
+			map.insert(key, arena[key] * 2);
+		}
+
+		for &key in &keys[3..] {
+			map.remove(key);
+		}
+
+		map.shrink_to(5);
+
+		assert_eq!(map.capacity(), 5);
+
+		map.shrink_to_fit();
+
+		assert_eq!(map.capacity(), 3);
+
+		for (_, value) in map.iter_mut() {
+			*value += 1;
+		}
+
+		assert!(map.iter().map(|(_, &value)| value).eq([1, 3, 5]));
+	}
+
+	#[test]
+	fn shrink_with_arena() {
+		let mut arena = Arena::<Id, u32>::new();
+		let mut map = SecondaryMap::<Id, u32>::new();
+
+		let keys: Vec<_> = (0..16).map(|i| arena.insert(i)).collect();
+
+		for &key in &keys {
+			map.insert(key, arena[key] * 2);
+		}
+
+		for &key in keys
+			.iter()
+			.filter(|key| key.index() % 2 == 1 || key.index() >= 8)
+		{
+			arena.remove(key);
+			map.remove(key);
+		}
+
+		let stale = keys[12];
+
+		map.insert(stale, 0);
+		arena.shrink_to_fit();
+		map.shrink_to(arena.capacity());
+
+		assert_eq!(arena.capacity(), 7);
+		assert_eq!(map.capacity(), 13);
+
+		for (key, &value) in arena.iter() {
+			assert_eq!(map.get(key), Some(&(value * 2)));
+		}
+
+		let grown: Vec<_> = (0..12).map(|i| arena.insert(i)).collect();
+
+		for &key in &grown {
+			map.insert(key, 1);
+		}
+
+		assert!(grown.iter().any(|key| key.index() == stale.index()));
+		assert_eq!(map.get(stale), None);
+		assert!(grown.iter().all(|&key| map.get(key) == Some(&1)));
+		assert!(arena.keys().all(|key| map.contains_key(key)));
+	}
+}
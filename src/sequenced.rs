@@ -0,0 +1,162 @@
+use alloc::collections::BTreeMap;
+
+use crate::{collection::Arena, referent::Referent};
+
+/// A [`SequencedArena`] is an [`Arena`] that numbers its values in the order they
+/// were inserted. Sequence numbers only ever increase and are never reused, so
+/// they order values by insertion even when slots are reused out of order.
+#[derive(Clone)]
+pub struct SequencedArena<Key: Referent, Value> {
+	arena: Arena<Key, (u64, Value)>,
+	order: BTreeMap<u64, Key>,
+	sequence: u64,
+}
+
+impl<Key: Referent, Value> Default for SequencedArena<Key, Value> {
+	#[inline]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<Key: Referent, Value> SequencedArena<Key, Value> {
+	/// Creates a new, empty [`SequencedArena`].
+	#[inline]
+	#[must_use]
+	pub const fn new() -> Self {
+		Self {
+			arena: Arena::new(),
+			order: BTreeMap::new(),
+			sequence: 0,
+		}
+	}
+
+	/// Returns the number of elements in the [`SequencedArena`].
+	#[inline]
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.arena.len()
+	}
+
+	/// Returns `true` if the [`SequencedArena`] contains no elements.
+	#[inline]
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.arena.is_empty()
+	}
+
+	/// Attempts to insert a value with the next sequence number, returning the
+	/// key if successful.
+	#[must_use]
+	pub fn try_insert(&mut self, value: Value) -> Option<Key> {
+		let key = self.arena.try_insert((self.sequence, value))?;
+
+		self.order.insert(self.sequence, key);
+		self.sequence += 1;
+
+		Some(key)
+	}
+
+	/// Inserts a value with the next sequence number, returning the key.
+	///
+	/// # Panics
+	///
+	/// Panics if the [`SequencedArena`] is at capacity.
+	#[inline]
+	#[must_use]
+	pub fn insert(&mut self, value: Value) -> Key {
+		self.try_insert(value).expect("should be able to insert")
+	}
+
+	/// Returns a reference to the value corresponding to the given key.
+	#[inline]
+	#[must_use]
+	pub fn get(&self, key: Key) -> Option<&Value> {
+		self.arena.get(key).map(|entry| &entry.1)
+	}
+
+	/// Returns a mutable reference to the value corresponding to the given key.
+	#[inline]
+	#[must_use]
+	pub fn get_mut(&mut self, key: Key) -> Option<&mut Value> {
+		self.arena.get_mut(key).map(|entry| &mut entry.1)
+	}
+
+	/// Returns the sequence number the value of the given key was inserted with.
+	#[inline]
+	#[must_use]
+	pub fn sequence_of(&self, key: Key) -> Option<u64> {
+		self.arena.get(key).map(|entry| entry.0)
+	}
+
+	/// Attempts to remove a key from the [`SequencedArena`], returning the value
+	/// if successful.
+	#[must_use]
+	pub fn try_remove(&mut self, key: Key) -> Option<Value> {
+		let (sequence, value) = self.arena.try_remove(key)?;
+
+		self.order.remove(&sequence);
+
+		Some(value)
+	}
+
+	/// Removes a key from the [`SequencedArena`], returning the value.
+	///
+	/// # Panics
+	///
+	/// Panics if the key is not present in the [`SequencedArena`].
+	#[inline]
+	pub fn remove(&mut self, key: Key) -> Value {
+		self.try_remove(key).expect("should be able to remove")
+	}
+
+	/// Returns an iterator over the keys and values of the [`SequencedArena`],
+	/// oldest first.
+	#[inline]
+	pub fn iter_by_sequence(&self) -> impl DoubleEndedIterator<Item = (Key, &Value)> {
+		self.order.values().map(|&key| (key, &self.arena[key].1))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		referent::{Id, Referent},
+		sequenced::SequencedArena,
+	};
+
+	#[test]
+	fn insertion_order() {
+		let mut arena = SequencedArena::<Id, &str>::new();
+
+		let a = arena.insert("a");
+		let b = arena.insert("b");
+		let c = arena.insert("c");
+
+		arena.remove(a);
+		arena.remove(c);
+
+		let d = arena.insert("d");
+		let e = arena.insert("e");
+
+		assert_eq!([d.index(), e.index()], [c.index(), a.index()]);
+		assert_eq!(arena.sequence_of(b), Some(1));
+		assert_eq!(arena.sequence_of(e), Some(4));
+		assert_eq!(arena.sequence_of(a), None);
+		assert!(arena
+			.iter_by_sequence()
+			.eq([(b, &"b"), (d, &"d"), (e, &"e")]));
+
+		*arena.get_mut(b).unwrap() = "B";
+
+		arena.remove(d);
+
+		let f = arena.insert("f");
+
+		assert_eq!(arena.sequence_of(f), Some(5));
+		assert!(arena
+			.iter_by_sequence()
+			.map(|(_, &value)| value)
+			.eq(["B", "e", "f"]));
+	}
+}
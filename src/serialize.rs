@@ -0,0 +1,311 @@
+use core::num::NonZeroU64;
+
+use alloc::vec::Vec;
+use serde::{
+	de::Error,
+	ser::{SerializeStruct, Serializer},
+	Deserialize, Deserializer, Serialize,
+};
+
+use crate::{
+	collection::Arena,
+	element::Element,
+	referent::{Referent, Similar},
+};
+
+/// The serialized form of a slot, with versions and indices widened to `u64`
+/// so that any key type can be used.
+#[derive(Serialize, Deserialize)]
+enum Slot<Value> {
+	Occupied { version: u64, value: Value },
+	Vacant { version: u64, next: u64 },
+	Tombstone { version: u64, value: Value },
+}
+
+#[derive(Deserialize)]
+struct Repr<Value> {
+	slots: Vec<Slot<Value>>,
+	len: u64,
+	next: u64,
+	version_floor: u64,
+}
+
+struct Slots<'a, Key: Referent, Value>(&'a Arena<Key, Value>);
+
+fn from_version<V: Similar<NonZeroU64>>(version: V) -> u64 {
+	version.try_into_unchecked().get()
+}
+
+fn from_index<I: Similar<usize>>(index: I) -> u64 {
+	index.try_into_unchecked() as u64
+}
+
+fn to_version<V: Similar<NonZeroU64>, E: Error>(version: u64) -> Result<V, E> {
+	NonZeroU64::new(version)
+		.and_then(V::try_from_checked)
+		.ok_or_else(|| E::custom("version is not representable"))
+}
+
+fn to_index<I: Similar<usize>, E: Error>(index: u64) -> Result<I, E> {
+	usize::try_from(index)
+		.ok()
+		.and_then(I::try_from_checked)
+		.ok_or_else(|| E::custom("index is not representable"))
+}
+
+/// Returns the free list link of a slot as if the quarantine had been released,
+/// with quarantined slots reused oldest first, followed by the free list.
+fn quarantine_link<Key: Referent, Value>(arena: &Arena<Key, Value>, index: usize) -> Option<u64> {
+	let position = arena
+		.quarantine
+		.iter()
+		.position(|&quarantined| quarantined.try_into_unchecked() == index)?;
+
+	let next = arena
+		.quarantine
+		.get(position + 1)
+		.copied()
+		.unwrap_or(arena.next);
+
+	Some(from_index(next))
+}
+
+impl<Key: Referent, Value: Serialize> Serialize for Slots<'_, Key, Value> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let elements = self.0.elements.iter().enumerate();
+
+		serializer.collect_seq(elements.map(|(index, element)| match element {
+			Element::Occupied { version, value } => Slot::Occupied {
+				version: from_version(*version),
+				value,
+			},
+			Element::Vacant { version, next } => Slot::Vacant {
+				version: from_version(*version),
+				next: quarantine_link(self.0, index).unwrap_or_else(|| from_index(*next)),
+			},
+			Element::Tombstone { version, value } => Slot::Tombstone {
+				version: from_version(*version),
+				value,
+			},
+		}))
+	}
+}
+
+/// Serializes every slot along with its version, and the free list, so that keys
+/// handed out before serializing resolve the same way after deserializing.
+/// Quarantined slots are written as part of the free list, ahead of it, and the
+/// version new slots start at is kept so that keys of truncated slots stay invalid.
+impl<Key: Referent, Value: Serialize> Serialize for Arena<Key, Value> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let mut state = serializer.serialize_struct("Arena", 4)?;
+
+		state.serialize_field("slots", &Slots(self))?;
+		state.serialize_field("len", &from_index(self.len))?;
+		state.serialize_field(
+			"next",
+			&from_index(self.quarantine.front().copied().unwrap_or(self.next)),
+		)?;
+		state.serialize_field("version_floor", &from_version(self.version_floor))?;
+		state.end()
+	}
+}
+
+/// Deserializes an [`Arena`] written by its [`Serialize`] implementation, rejecting
+/// any whose length or free list does not match its slots.
+impl<'de, Key: Referent, Value: Deserialize<'de>> Deserialize<'de> for Arena<Key, Value> {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		let repr = Repr::<Value>::deserialize(deserializer)?;
+
+		to_index::<Key::Index, D::Error>(repr.slots.len() as u64)?;
+
+		let elements: Vec<Element<Key::Version, Key::Index, Value>> = repr
+			.slots
+			.into_iter()
+			.map(|slot| {
+				Ok(match slot {
+					Slot::Occupied { version, value } => Element::Occupied {
+						version: to_version(version)?,
+						value,
+					},
+					Slot::Vacant { version, next } => Element::Vacant {
+						version: to_version(version)?,
+						next: to_index(next)?,
+					},
+					Slot::Tombstone { version, value } => Element::Tombstone {
+						version: to_version(version)?,
+						value,
+					},
+				})
+			})
+			.collect::<Result<_, D::Error>>()?;
+
+		let occupied = elements
+			.iter()
+			.filter(|element| element.as_ref().is_some())
+			.count();
+
+		if repr.len != occupied as u64 {
+			return Err(D::Error::custom("length does not match the occupied slots"));
+		}
+
+		let vacant = elements
+			.iter()
+			.filter(|element| matches!(element, Element::Vacant { .. }))
+			.count();
+
+		let mut visited = 0;
+		let mut next: usize = to_index::<Key::Index, D::Error>(repr.next)?.try_into_unchecked();
+
+		while let Some(element) = elements.get(next) {
+			let Element::Vacant { next: link, .. } = element else {
+				return Err(D::Error::custom(
+					"free list points at a slot that is not vacant",
+				));
+			};
+
+			visited += 1;
+
+			if visited > vacant {
+				return Err(D::Error::custom("free list contains a cycle"));
+			}
+
+			next = link.try_into_unchecked();
+		}
+
+		if next != elements.len() || visited != vacant {
+			return Err(D::Error::custom(
+				"free list does not cover every vacant slot",
+			));
+		}
+
+		let tombstones = elements
+			.iter()
+			.filter(|element| matches!(element, Element::Tombstone { .. }))
+			.count();
+
+		let mut arena = Self {
+			elements,
+			len: to_index(repr.len)?,
+			tombstones,
+			next: to_index(repr.next)?,
+			version_floor: to_version(repr.version_floor)?,
+			..Self::default()
+		};
+
+		arena.fill_vacant();
+
+		arena.max_occupied = arena
+			.elements
+			.len()
+			.checked_sub(1)
+			.and_then(Key::Index::try_from_checked);
+
+		arena.shrink_max_occupied();
+
+		Ok(arena)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		collection::Arena,
+		referent::{Id, Nil, Referent},
+	};
+
+	#[test]
+	fn round_trip() {
+		let mut arena = Arena::<Id, String>::new();
+
+		let keys: Vec<_> = (0..6).map(|i| arena.insert(i.to_string())).collect();
+
+		arena.remove(keys[1]);
+		arena.remove(keys[4]);
+
+		let stale = arena.insert("reused".into());
+		let _tomb = arena.remove_tombstoned(keys[5]);
+
+		let json = serde_json::to_string(&arena).unwrap();
+		let mut copy: Arena<Id, String> = serde_json::from_str(&json).unwrap();
+
+		assert_eq!(copy.len(), arena.len());
+		assert_eq!(copy.get(keys[1]), None);
+		assert_eq!(copy.get(keys[4]), None);
+		assert_eq!(copy.get(keys[5]), None);
+		assert_eq!(copy.get_tombstone(keys[5]).map(String::as_str), Some("5"));
+		assert_eq!(copy[stale], "reused");
+		assert_eq!(copy[keys[0]], "0");
+		assert_eq!(copy[keys[3]], "3");
+		assert_eq!(copy.insert("a".into()), arena.insert("a".into()));
+		assert_eq!(copy.insert("b".into()), arena.insert("b".into()));
+		assert!(copy.iter().eq(arena.iter()));
+	}
+
+	#[test]
+	fn reject_corrupted() {
+		let mut arena = Arena::<Id<u32, Nil>, u32>::new();
+
+		let a = arena.insert(10);
+		let _b = arena.insert(20);
+
+		arena.remove(a);
+
+		let json = serde_json::to_string(&arena).unwrap();
+
+		for (from, to) in [(r#""len":1"#, r#""len":2"#), (r#""next":0"#, r#""next":1"#)] {
+			let corrupted = json.replace(from, to);
+
+			assert!(serde_json::from_str::<Arena<Id<u32, Nil>, u32>>(&corrupted).is_err());
+		}
+
+		let copy: Arena<Id<u32, Nil>, u32> = serde_json::from_str(&json).unwrap();
+
+		assert_eq!(copy.len(), 1);
+	}
+
+	#[test]
+	fn round_trip_after_shrink() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		let _a = arena.insert(1);
+		let b = arena.insert(2);
+
+		arena.remove(b);
+
+		let c = arena.insert(3);
+
+		arena.remove(c);
+		arena.shrink_to_fit();
+
+		let json = serde_json::to_string(&arena).unwrap();
+		let mut copy: Arena<Id, u32> = serde_json::from_str(&json).unwrap();
+
+		let key = copy.insert(4);
+
+		assert_eq!(key, arena.insert(4));
+		assert_ne!(key, b);
+		assert_ne!(key, c);
+		assert_eq!(copy.get(b), None);
+		assert_eq!(copy.get(c), None);
+	}
+
+	#[test]
+	fn release_quarantine() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		arena.set_quarantine(2);
+
+		let keys: Vec<_> = (0..4).map(|i| arena.insert(i)).collect();
+
+		arena.remove(keys[1]);
+		arena.remove(keys[2]);
+
+		let json = serde_json::to_string(&arena).unwrap();
+		let mut copy: Arena<Id, u32> = serde_json::from_str(&json).unwrap();
+
+		assert_eq!(copy.quarantined(), 0);
+		assert_eq!(copy.insert(10).index(), keys[1].index());
+		assert_eq!(copy.insert(20).index(), keys[2].index());
+		assert_eq!(copy.insert(30).index(), 4);
+	}
+}
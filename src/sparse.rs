@@ -0,0 +1,130 @@
+use hashbrown::HashMap;
+
+use crate::referent::{Referent, Similar};
+
+/// A [`SparseSecondaryMap`] associates extra data with the keys of an
+/// [`Arena`](crate::collection::Arena), without storing it in the arena itself.
+/// It is backed by a hash map, so it suits data attached to only a few keys,
+/// and works in `no_std` environments.
+///
+/// Entries are tied to the version of the key they were inserted with, so
+/// keys of removed elements do not resolve to data of newer ones.
+pub struct SparseSecondaryMap<Key: Referent, Value> {
+	map: HashMap<usize, (Key::Version, Value)>,
+}
+
+impl<Key: Referent, Value> Default for SparseSecondaryMap<Key, Value> {
+	#[inline]
+	fn default() -> Self {
+		Self {
+			map: HashMap::default(),
+		}
+	}
+}
+
+impl<Key: Referent, Value> SparseSecondaryMap<Key, Value> {
+	/// Creates a new, empty [`SparseSecondaryMap`].
+	#[inline]
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Returns the number of elements in the [`SparseSecondaryMap`].
+	#[inline]
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.map.len()
+	}
+
+	/// Returns `true` if the [`SparseSecondaryMap`] contains no elements.
+	#[inline]
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.map.is_empty()
+	}
+
+	/// Inserts a value for the given key, returning the previous value of the
+	/// same key. Keys older than the one already present are ignored.
+	#[inline]
+	pub fn insert(&mut self, key: Key, value: Value) -> Option<Value> {
+		let index = key.index().try_into_unchecked();
+		let version = key.version();
+
+		match self.map.get_mut(&index) {
+			Some(entry) => {
+				let current = entry.0.try_into_unchecked();
+				let parameter = version.try_into_unchecked();
+
+				if parameter < current {
+					return None;
+				}
+
+				let last = core::mem::replace(entry, (version, value));
+
+				(parameter == current).then_some(last.1)
+			}
+			None => {
+				self.map.insert(index, (version, value));
+
+				None
+			}
+		}
+	}
+
+	/// Returns a reference to the value corresponding to the given key.
+	#[inline]
+	#[must_use]
+	pub fn get(&self, key: Key) -> Option<&Value> {
+		let entry = self.map.get(&key.index().try_into_unchecked())?;
+
+		(entry.0.try_into_unchecked() == key.version().try_into_unchecked()).then_some(&entry.1)
+	}
+
+	/// Returns a mutable reference to the value corresponding to the given key.
+	#[inline]
+	#[must_use]
+	pub fn get_mut(&mut self, key: Key) -> Option<&mut Value> {
+		let entry = self.map.get_mut(&key.index().try_into_unchecked())?;
+
+		(entry.0.try_into_unchecked() == key.version().try_into_unchecked()).then_some(&mut entry.1)
+	}
+
+	/// Removes a key from the [`SparseSecondaryMap`], returning the value if present.
+	#[inline]
+	pub fn remove(&mut self, key: Key) -> Option<Value> {
+		let index = key.index().try_into_unchecked();
+
+		self.get(key)?;
+		self.map.remove(&index).map(|entry| entry.1)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{collection::Arena, referent::Id, sparse::SparseSecondaryMap};
+
+	#[test]
+	fn versioned_entries() {
+		let mut arena = Arena::<Id, u32>::new();
+		let mut map = SparseSecondaryMap::<Id, &str>::new();
+
+		let a = arena.insert(10);
+
+		assert_eq!(map.insert(a, "a"), None);
+		assert_eq!(map.insert(a, "A"), Some("a"));
+		assert_eq!(map.get(a), Some(&"A"));
+
+		arena.remove(a);
+
+		let b = arena.insert(20);
+
+		assert_eq!(map.get(b), None);
+		assert_eq!(map.insert(b, "b"), None);
+		assert_eq!(map.insert(a, "stale"), None);
+		assert_eq!(map.get(a), None);
+		assert_eq!(map.remove(a), None);
+		assert_eq!(map.remove(b), Some("b"));
+		assert!(map.is_empty());
+	}
+}
@@ -0,0 +1,157 @@
+use core::{
+	num::NonZeroU64,
+	sync::atomic::{AtomicU64, Ordering},
+};
+
+use crate::{
+	collection::Arena,
+	element::Element,
+	referent::{Referent, Similar},
+};
+
+/// Counters of failed lookups in an [`Arena`], split by the reason of the miss.
+///
+/// Returned by the [`Arena::lookup_stats`] method. The counters are updated
+/// atomically through a shared reference, so [`Arena`] stays `Sync`.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, Default)]
+pub struct LookupStats {
+	/// Lookups of an occupied slot with a different version.
+	pub stale_misses: u64,
+	/// Lookups of a vacant slot.
+	pub vacant_misses: u64,
+	/// Lookups of a slot past the capacity.
+	pub out_of_range_misses: u64,
+}
+
+/// The counters kept by an [`Arena`]. They are only ever added to, so relaxed
+/// ordering is enough.
+pub(crate) struct AtomicLookupStats {
+	stale_misses: AtomicU64,
+	vacant_misses: AtomicU64,
+	out_of_range_misses: AtomicU64,
+}
+
+impl AtomicLookupStats {
+	pub(crate) const fn new() -> Self {
+		Self {
+			stale_misses: AtomicU64::new(0),
+			vacant_misses: AtomicU64::new(0),
+			out_of_range_misses: AtomicU64::new(0),
+		}
+	}
+
+	pub(crate) fn load(&self) -> LookupStats {
+		LookupStats {
+			stale_misses: self.stale_misses.load(Ordering::Relaxed),
+			vacant_misses: self.vacant_misses.load(Ordering::Relaxed),
+			out_of_range_misses: self.out_of_range_misses.load(Ordering::Relaxed),
+		}
+	}
+
+	pub(crate) fn record<Version, Index, Value>(
+		&self,
+		element: Option<&Element<Version, Index, Value>>,
+		version: Version,
+	) where
+		Version: Similar<NonZeroU64> + Copy,
+		Index: Similar<usize> + Copy,
+	{
+		let counter = match element {
+			Some(Element::Occupied { version: other, .. }) => {
+				if other.try_into_unchecked() == version.try_into_unchecked() {
+					return;
+				}
+
+				&self.stale_misses
+			}
+			Some(Element::Tombstone { .. }) => &self.stale_misses,
+			Some(Element::Vacant { .. }) => &self.vacant_misses,
+			None => &self.out_of_range_misses,
+		};
+
+		counter.fetch_add(1, Ordering::Relaxed);
+	}
+}
+
+impl Clone for AtomicLookupStats {
+	#[inline]
+	fn clone(&self) -> Self {
+		let stats = self.load();
+
+		Self {
+			stale_misses: AtomicU64::new(stats.stale_misses),
+			vacant_misses: AtomicU64::new(stats.vacant_misses),
+			out_of_range_misses: AtomicU64::new(stats.out_of_range_misses),
+		}
+	}
+}
+
+impl<Key: Referent, Value> Arena<Key, Value> {
+	/// Returns the counters of failed lookups done through [`Arena::get`]
+	/// and [`Arena::get_mut`].
+	#[inline]
+	#[must_use]
+	pub fn lookup_stats(&self) -> LookupStats {
+		self.stats.load()
+	}
+
+	/// Resets the counters of failed lookups to zero.
+	#[inline]
+	pub fn reset_lookup_stats(&mut self) {
+		self.stats = AtomicLookupStats::new();
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{collection::Arena, referent::Id, stats::LookupStats};
+
+	#[test]
+	fn lookup_misses() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		let a = arena.insert(10);
+		let b = arena.insert(20);
+
+		arena.remove(a);
+
+		let c = arena.insert(30);
+
+		arena.remove(b);
+
+		assert_eq!(arena.get(c), Some(&30));
+		assert_eq!(arena.get(a), None);
+		assert_eq!(arena.get_mut(a), None);
+		assert_eq!(arena.get(b), None);
+		assert_eq!(arena.get(Id::dangling()), None);
+
+		let expected = LookupStats {
+			stale_misses: 2,
+			vacant_misses: 1,
+			out_of_range_misses: 1,
+		};
+
+		assert_eq!(arena.lookup_stats(), expected);
+
+		arena.reset_lookup_stats();
+
+		assert_eq!(arena.lookup_stats(), LookupStats::default());
+	}
+
+	#[test]
+	fn shared_across_threads() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		let a = arena.insert(10);
+
+		arena.remove(a);
+
+		std::thread::scope(|scope| {
+			for _ in 0..4 {
+				scope.spawn(|| assert_eq!(arena.get(a), None));
+			}
+		});
+
+		assert_eq!(arena.lookup_stats().vacant_misses, 4);
+	}
+}
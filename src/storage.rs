@@ -0,0 +1,74 @@
+use crate::{collection::Arena, referent::Referent};
+
+/// A keyed storage of values, for code that is generic over the container.
+pub trait Storage {
+	/// The type of the keys handed out by the storage.
+	type Key;
+
+	/// The type of the stored values.
+	type Value;
+
+	/// Returns a reference to the value corresponding to the given key.
+	fn get(&self, key: Self::Key) -> Option<&Self::Value>;
+
+	/// Returns a mutable reference to the value corresponding to the given key.
+	fn get_mut(&mut self, key: Self::Key) -> Option<&mut Self::Value>;
+
+	/// Inserts a value into the storage, returning the key.
+	fn insert(&mut self, value: Self::Value) -> Self::Key;
+
+	/// Removes a key from the storage, returning the value if present.
+	fn remove(&mut self, key: Self::Key) -> Option<Self::Value>;
+}
+
+impl<Key: Referent, Value> Storage for Arena<Key, Value> {
+	type Key = Key;
+	type Value = Value;
+
+	#[inline]
+	fn get(&self, key: Key) -> Option<&Value> {
+		self.get(key)
+	}
+
+	#[inline]
+	fn get_mut(&mut self, key: Key) -> Option<&mut Value> {
+		self.get_mut(key)
+	}
+
+	#[inline]
+	fn insert(&mut self, value: Value) -> Key {
+		self.insert(value)
+	}
+
+	#[inline]
+	fn remove(&mut self, key: Key) -> Option<Value> {
+		self.try_remove(key)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{collection::Arena, referent::Id, storage::Storage};
+
+	fn bump<S: Storage<Value = u32>>(storage: &mut S, value: u32) -> Option<u32>
+	where
+		S::Key: Copy,
+	{
+		let key = storage.insert(value);
+
+		*storage.get_mut(key)? += 1;
+
+		let value = *storage.get(key)?;
+
+		storage.remove(key)?;
+		storage.get(key).is_none().then_some(value)
+	}
+
+	#[test]
+	fn generic_storage() {
+		let mut arena = Arena::<Id, u32>::new();
+
+		assert_eq!(bump(&mut arena, 10), Some(11));
+		assert!(arena.is_empty());
+	}
+}
@@ -0,0 +1,132 @@
+use crate::{collection::Arena, referent::Referent};
+
+/// A [`TtlArena`] is an [`Arena`] whose values expire at a given tick.
+/// Expired values are treated as absent, and their slots are reclaimed
+/// by [`TtlArena::sweep`].
+#[derive(Clone)]
+pub struct TtlArena<Key: Referent, Value> {
+	arena: Arena<Key, (u64, Value)>,
+}
+
+impl<Key: Referent, Value> Default for TtlArena<Key, Value> {
+	#[inline]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<Key: Referent, Value> TtlArena<Key, Value> {
+	/// Creates a new, empty [`TtlArena`].
+	#[inline]
+	#[must_use]
+	pub const fn new() -> Self {
+		Self {
+			arena: Arena::new(),
+		}
+	}
+
+	/// Returns the number of elements in the [`TtlArena`], including
+	/// expired ones that were not swept yet.
+	#[inline]
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.arena.len()
+	}
+
+	/// Returns `true` if the [`TtlArena`] contains no elements.
+	#[inline]
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.arena.is_empty()
+	}
+
+	/// Inserts a value that expires once the time reaches `expiry`, returning the key.
+	///
+	/// # Panics
+	///
+	/// Panics if the [`TtlArena`] is at capacity.
+	#[inline]
+	#[must_use]
+	pub fn insert_ttl(&mut self, value: Value, expiry: u64) -> Key {
+		self.arena.insert((expiry, value))
+	}
+
+	/// Returns a reference to the value corresponding to the given key,
+	/// unless it has expired at time `now`.
+	#[inline]
+	#[must_use]
+	pub fn get(&self, key: Key, now: u64) -> Option<&Value> {
+		self.arena
+			.get(key)
+			.filter(|entry| now < entry.0)
+			.map(|entry| &entry.1)
+	}
+
+	/// Returns a mutable reference to the value corresponding to the given key,
+	/// unless it has expired at time `now`.
+	#[inline]
+	#[must_use]
+	pub fn get_mut(&mut self, key: Key, now: u64) -> Option<&mut Value> {
+		self.arena
+			.get_mut(key)
+			.filter(|entry| now < entry.0)
+			.map(|entry| &mut entry.1)
+	}
+
+	/// Returns the expiry time of the value corresponding to the given key.
+	#[inline]
+	#[must_use]
+	pub fn expiry(&self, key: Key) -> Option<u64> {
+		self.arena.get(key).map(|entry| entry.0)
+	}
+
+	/// Attempts to remove a key from the [`TtlArena`], returning the value if
+	/// successful. Expired values that were not swept yet are also returned.
+	#[inline]
+	#[must_use]
+	pub fn try_remove(&mut self, key: Key) -> Option<Value> {
+		self.arena.try_remove(key).map(|entry| entry.1)
+	}
+
+	/// Removes all values that have expired at time `now`, returning how many were removed.
+	#[inline]
+	pub fn sweep(&mut self, now: u64) -> usize {
+		let len = self.arena.len();
+
+		self.arena.retain(|_, entry| now < entry.0);
+
+		len - self.arena.len()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		referent::{Id, Referent},
+		ttl::TtlArena,
+	};
+
+	#[test]
+	fn expire_and_sweep() {
+		let mut arena = TtlArena::<Id, u32>::new();
+
+		let a = arena.insert_ttl(10, 5);
+		let b = arena.insert_ttl(20, 10);
+
+		assert_eq!(arena.get(a, 4), Some(&10));
+		assert_eq!(arena.get(a, 5), None);
+		assert_eq!(arena.get(b, 5), Some(&20));
+		assert_eq!(arena.len(), 2);
+
+		assert_eq!(arena.sweep(5), 1);
+		assert_eq!(arena.len(), 1);
+		assert_eq!(arena.expiry(a), None);
+
+		let c = arena.insert_ttl(30, 15);
+
+		assert_eq!(a.index(), c.index());
+		assert_eq!(arena.get(c, 5), Some(&30));
+		assert_eq!(arena.sweep(20), 2);
+		assert!(arena.is_empty());
+	}
+}
@@ -0,0 +1,36 @@
+//! Checks that the crate, built as `no_std`, can be used from a `no_std` crate.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use arena::{
+	collection::Arena,
+	referent::{Id, Referent},
+	sparse::SparseSecondaryMap,
+};
+
+#[test]
+fn sparse_secondary_map() {
+	let mut arena = Arena::<Id, u32>::new();
+	let mut map = SparseSecondaryMap::<Id, Vec<u8>>::new();
+
+	let a = arena.insert(10);
+	let b = arena.insert(20);
+
+	assert_eq!(map.insert(a, Vec::from([1, 2])), None);
+	assert_eq!(map.insert(b, Vec::from([3])), None);
+	assert_eq!(map.get(a).map(Vec::as_slice), Some(&[1, 2][..]));
+
+	arena.remove(a);
+
+	let c = arena.insert(30);
+
+	assert_eq!(c.index(), a.index());
+	assert_eq!(map.get(c), None);
+	assert_eq!(map.remove(a), Some(Vec::from([1, 2])));
+	assert_eq!(map.remove(b), Some(Vec::from([3])));
+	assert!(map.is_empty());
+}